@@ -27,6 +27,18 @@ module.exports = merge({
         test: /\.tsx?$/,
         use: [{ loader: 'ts-loader', options: { transpileOnly: true } }],
       },
+      {
+        test: /\.(atlas\.json|fnt)$/,
+        type: 'javascript/auto',
+        use: [
+          {
+            loader: 'file-loader',
+            options: {
+              name: '[name].[ext]',
+            },
+          },
+        ],
+      },
       {
         test: /\.(png|jpe?g|gif)/,
         use: [
@@ -2,7 +2,10 @@ import wasm from '../pkg/index_bg.wasm';
 import pkg from '../pkg';
 import '../assets/sample_texture.png';
 import '../assets/entities0.png';
+import '../assets/entities0.atlas.json';
 import '../assets/background.png';
+import '../assets/font.png';
+import '../assets/font.fnt';
 
 Promise.all([pkg, wasm]).then(([pkg]) => {
   // eslint-disable-next-line no-console
@@ -80,7 +80,7 @@ impl ParametricCurveSequence for BezierSequence {
         let curve = self.curves.iter().find_map(|curve| {
             let next_base = base + curve.t_duration;
             if t <= next_base {
-                let t = (t - next_base) / curve.t_duration;
+                let t = (t - base) / curve.t_duration;
                 Some((t, curve))
             } else {
                 base = next_base;
@@ -98,3 +98,30 @@ impl ParametricCurveSequence for BezierSequence {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(from: f32, to: f32) -> Curve {
+        Curve {
+            t_duration: 1.,
+            t_p: None,
+            p_0: [from, 0., 0.],
+            p_1: [from + (to - from) / 3., 0., 0.],
+            p_2: [from + (to - from) * 2. / 3., 0., 0.],
+            p_3: [to, 0., 0.],
+        }
+    }
+
+    #[test]
+    fn each_curve_is_evaluated_from_its_own_start() {
+        let sequence = BezierSequence::new(vec![line(0., 1.), line(1., 3.)]);
+        let x = |t| sequence.calc_point(t, false).unwrap()[0];
+        assert!((x(0.) - 0.).abs() < 1e-5);
+        assert!((x(0.5) - 0.5).abs() < 1e-5);
+        assert!((x(1.5) - 2.).abs() < 1e-5);
+        assert!((x(2.) - 3.).abs() < 1e-5);
+        assert!(sequence.calc_point(2.5, false).is_none());
+    }
+}
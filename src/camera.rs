@@ -1,17 +1,31 @@
+mod effects;
+mod rig;
+mod ui;
+
+pub use effects::CameraEffects;
+pub use rig::{CameraDirector, Rig, RigTarget};
+pub use ui::{Anchor, UiCameraController};
+
 use crate::impls::matrix::ViewMatrix;
+use crate::impls::vector::Normalize;
 use crate::ConvertArrayView;
 use std::f32::consts::PI;
-use webgl_matrix::{Mat4, Matrix, ProjectionMatrix, Vec3};
+use webgl_matrix::{Mat4, Matrix, MulVectorMatrix, ProjectionMatrix, Vec3, Vector};
 
 #[repr(C)]
 pub struct Camera {
     pub vp_matrix: Mat4,
+    /// Lets shaders turn sprites toward the camera.
+    pub view_matrix: Mat4,
+    /// Width over height of the viewport, for offsets measured on the screen.
+    pub aspect_ratio: f32,
+    _pad0: [u32; 3],
 }
 impl ConvertArrayView for Camera {}
 
 pub struct CameraController {
     pub camera: Camera,
-    projection: Projection,
+    pub projection: Projection,
     pub view: View,
 }
 impl Default for CameraController {
@@ -20,7 +34,12 @@ impl Default for CameraController {
         let view = View::default();
         let vp_matrix = Mat4::identity();
         let mut s = Self {
-            camera: Camera { vp_matrix },
+            camera: Camera {
+                vp_matrix,
+                view_matrix: Mat4::identity(),
+                aspect_ratio: 1.,
+                _pad0: [0; 3],
+            },
             projection,
             view,
         };
@@ -32,8 +51,45 @@ impl CameraController {
     pub fn refresh(&mut self) {
         self.projection.refresh();
         self.view.refresh();
+        self.view.matrix.copy_to(&mut self.camera.view_matrix);
         self.view.matrix.copy_to(&mut self.camera.vp_matrix);
         self.camera.vp_matrix.mul(&self.projection.matrix);
+        self.camera.aspect_ratio = self.projection.aspect_retio;
+    }
+
+    /// Casts a ray from the camera through `coord` given in normalized device coordinates.
+    pub fn screen_to_ray(&self, coord: (f32, f32)) -> Option<Ray> {
+        let mut vp_inv = self.camera.vp_matrix;
+        vp_inv.inverse()?;
+        let p = [coord.0, coord.1, -1., 1.].mul_matrix(&vp_inv);
+        let p = [p[0] / p[3], p[1] / p[3], p[2] / p[3]];
+        Some(Ray {
+            origin: self.view.position,
+            direction: p.sub(&self.view.position).normalize(),
+        })
+    }
+
+    /// Picks the point on the plane through `point` with `normal` that is under `coord`.
+    pub fn pick_plane(&self, coord: (f32, f32), point: &Vec3, normal: &Vec3) -> Option<Vec3> {
+        self.screen_to_ray(coord)?.intersect_plane(point, normal)
+    }
+}
+
+pub struct Ray {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+impl Ray {
+    pub fn intersect_plane(&self, point: &Vec3, normal: &Vec3) -> Option<Vec3> {
+        let denom = normal.dot(&self.direction);
+        if denom.abs() < f32::EPSILON {
+            return None;
+        }
+        let distance = normal.dot(&point.sub(&self.origin)) / denom;
+        if distance < 0. {
+            return None;
+        }
+        Some(self.origin.add(&self.direction.scale(distance)))
     }
 }
 
@@ -64,10 +120,12 @@ impl View {
 
 pub struct Projection {
     matrix: Mat4,
-    fov_y: f32,
-    aspect_retio: f32,
-    near: f32,
-    far: f32,
+    pub fov_y: f32,
+    /// Divides `fov_y`, zooming in above 1.
+    pub zoom: f32,
+    pub aspect_retio: f32,
+    pub near: f32,
+    pub far: f32,
 }
 impl Default for Projection {
     fn default() -> Self {
@@ -78,6 +136,7 @@ impl Default for Projection {
         Self {
             matrix: Mat4::create_perspective(fov_y, aspect_retio, near, far),
             fov_y,
+            zoom: 1.,
             aspect_retio,
             near,
             far,
@@ -86,6 +145,11 @@ impl Default for Projection {
 }
 impl Projection {
     fn refresh(&mut self) {
-        self.matrix = Mat4::create_perspective(self.fov_y, self.aspect_retio, self.near, self.far);
+        self.matrix = Mat4::create_perspective(
+            self.fov_y / self.zoom,
+            self.aspect_retio,
+            self.near,
+            self.far,
+        );
     }
 }
@@ -0,0 +1,115 @@
+use super::CameraController;
+use crate::impls::vector::Normalize;
+use crate::random::Random;
+
+use std::f32::consts::PI;
+use webgl_matrix::{Vec3, Vector};
+
+struct FovPunch {
+    zoom: f32,
+    started_at: f32,
+    duration: f32,
+}
+
+/// Shake and zoom put on top of the shot of the active rig. Effects run on their own clock, which
+/// starts with the first frame.
+pub struct CameraEffects {
+    /// Strength of the shake in `[0, 1]`. The shake grows with its square.
+    trauma: f32,
+    /// Trauma lost per second of the effect clock.
+    trauma_decay: f32,
+    max_shake_offset: f32,
+    /// Radians.
+    max_shake_angle: f32,
+    /// Phases of the noise of each axis of the offset and the angle.
+    phases: [f32; 6],
+    fov_punch: Option<FovPunch>,
+    /// Effect clock in milliseconds.
+    time: f32,
+    last_frame: Option<f32>,
+}
+
+impl CameraEffects {
+    pub fn new(trauma_decay: f32, max_shake_offset: f32, max_shake_angle: f32, seed: u32) -> Self {
+        let mut random = Random::new(seed);
+        let mut phases = [0.; 6];
+        for phase in &mut phases {
+            *phase = random.range(0., 2. * PI);
+        }
+        Self {
+            trauma: 0.,
+            trauma_decay,
+            max_shake_offset,
+            max_shake_angle,
+            phases,
+            fov_punch: None,
+            time: 0.,
+            last_frame: None,
+        }
+    }
+
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0., 1.);
+    }
+
+    /// Zooms in by `zoom` at once and back out over `duration` milliseconds of the effect clock.
+    pub fn punch_fov(&mut self, zoom: f32, duration: f32) {
+        self.fov_punch = Some(FovPunch {
+            zoom,
+            started_at: self.time,
+            duration,
+        });
+    }
+
+    /// Advances the effect clock to the frame at `time`.
+    pub fn update(&mut self, time: f32) {
+        let delta = match self.last_frame {
+            Some(last_frame) => (time - last_frame).max(0.),
+            None => 0.,
+        };
+        self.last_frame = Some(time);
+        self.time += delta;
+        self.trauma = (self.trauma - self.trauma_decay * delta / 1000.).max(0.);
+        if let Some(punch) = &self.fov_punch {
+            if self.time - punch.started_at >= punch.duration {
+                self.fov_punch = None;
+            }
+        }
+    }
+
+    /// Puts the effects on the view and the projection of `camera`, which the rig has just
+    /// pointed.
+    pub fn apply(&self, camera: &mut CameraController) {
+        camera.projection.zoom = match &self.fov_punch {
+            Some(punch) => {
+                // eases back out from the full zoom
+                let t = ((self.time - punch.started_at) / punch.duration).clamp(0., 1.);
+                1. + (punch.zoom - 1.) * (1. - t) * (1. - t)
+            }
+            None => 1.,
+        };
+
+        let shake = self.trauma * self.trauma;
+        if shake <= 0. {
+            return;
+        }
+        let noise = |i: usize| {
+            let t = self.time / 1000.;
+            // sum of incommensurate waves, which looks random but stays smooth
+            ((t * 23. + self.phases[i]).sin() + (t * 37.3 + self.phases[i] * 2.).sin()) * 0.5
+        };
+        let offset: Vec3 = [noise(0), noise(1), noise(2)].scale(self.max_shake_offset * shake);
+        let tilt: Vec3 = [noise(3), noise(4), noise(5)].scale(self.max_shake_angle * shake);
+        let view = &mut camera.view;
+        view.position = view.position.add(&offset);
+        view.direction = view.direction.add(&tilt).normalize();
+    }
+}
+
+// Concrete Instances
+
+impl CameraEffects {
+    pub fn default(seed: u32) -> Self {
+        Self::new(1.5, 0.08, 0.03, seed)
+    }
+}
@@ -0,0 +1,153 @@
+use super::CameraController;
+use crate::impls::vector::Normalize;
+
+use std::f32::consts::PI;
+use webgl_matrix::{Vec3, Vector};
+
+/// Where the camera is and where it looks.
+#[derive(Clone, Copy)]
+pub struct Shot {
+    pub position: Vec3,
+    pub direction: Vec3,
+}
+
+/// What the rigs frame.
+pub struct RigTarget {
+    pub batter: Vec3,
+    pub pitcher: Vec3,
+    /// Last known position of the ball.
+    pub ball: Vec3,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Rig {
+    /// Behind the batter, looking toward the pitcher.
+    Batter,
+    /// Centre field, looking over the pitcher toward the plate.
+    Broadcast,
+    /// Chases the ball from behind and above.
+    FollowBall,
+    /// Looks down on the whole field.
+    Overhead,
+}
+
+struct Transition {
+    from: Shot,
+    started_at: f32,
+}
+
+/// Points the camera with one of the rigs and eases from the last shot whenever the rig is
+/// changed.
+pub struct CameraDirector {
+    rig: Rig,
+    /// Milliseconds taken to move from one rig to another.
+    blend_duration: f32,
+    transition: Option<Transition>,
+    shot: Shot,
+}
+
+impl Rig {
+    /// Rigs cycled through by the player.
+    pub const SELECTABLE: [Rig; 3] = [Rig::Batter, Rig::Broadcast, Rig::Overhead];
+
+    pub fn shot(&self, target: &RigTarget) -> Shot {
+        match self {
+            Rig::Batter => {
+                let theta = PI / 10.;
+                Shot {
+                    position: [0., 0., 3.],
+                    direction: [0., -theta.sin(), -theta.cos()],
+                }
+            }
+            Rig::Broadcast => {
+                let position = target.pitcher.add(&[0.4, 1.4, -5.]);
+                look_at(position, target.batter)
+            }
+            Rig::FollowBall => {
+                let [x, y, z] = target.ball;
+                look_at([x * 0.5, (y + 1.).max(0.5), z + 4.], target.ball)
+            }
+            Rig::Overhead => {
+                let center = target.batter.add(&target.pitcher).scale(0.5);
+                look_at(center.add(&[0., 12., 3.]), center)
+            }
+        }
+    }
+}
+
+impl CameraDirector {
+    pub fn new(rig: Rig, blend_duration: f32) -> Self {
+        Self {
+            rig,
+            blend_duration,
+            transition: None,
+            shot: Rig::Batter.shot(&RigTarget {
+                batter: [0., 0., 0.],
+                pitcher: [0., 0., 0.],
+                ball: [0., 0., 0.],
+            }),
+        }
+    }
+
+    pub fn rig(&self) -> Rig {
+        self.rig
+    }
+
+    /// Moves on to `rig` from the current shot. Nothing happens if it is already the rig.
+    pub fn cut(&mut self, time: f32, rig: Rig) {
+        if rig == self.rig {
+            return;
+        }
+        self.rig = rig;
+        self.transition = Some(Transition {
+            from: self.shot,
+            started_at: time,
+        });
+    }
+
+    /// Points `camera` at the shot of the rig, blended with the previous one while moving.
+    pub fn update(&mut self, time: f32, target: &RigTarget, camera: &mut CameraController) {
+        let shot = self.rig.shot(target);
+        let weight = match &self.transition {
+            Some(transition) if self.blend_duration > 0. => {
+                ((time - transition.started_at) / self.blend_duration).clamp(0., 1.)
+            }
+            _ => 1.,
+        };
+        self.shot = match &self.transition {
+            Some(transition) if weight < 1. => blend(&transition.from, &shot, ease(weight)),
+            _ => {
+                self.transition = None;
+                shot
+            }
+        };
+        camera.view.position = self.shot.position;
+        camera.view.direction = self.shot.direction;
+    }
+}
+
+fn look_at(position: Vec3, target: Vec3) -> Shot {
+    Shot {
+        position,
+        direction: target.sub(&position).normalize(),
+    }
+}
+
+fn blend(from: &Shot, to: &Shot, weight: f32) -> Shot {
+    let mix = |a: &Vec3, b: &Vec3| a.scale(1. - weight).add(&b.scale(weight));
+    let direction = mix(&from.direction, &to.direction);
+    Shot {
+        position: mix(&from.position, &to.position),
+        // opposite directions cancel each other out
+        direction: if direction.mag() > f32::EPSILON {
+            direction.normalize()
+        } else {
+            to.direction
+        },
+    }
+}
+
+/// Smoothstep, which starts and ends the move slowly.
+fn ease(t: f32) -> f32 {
+    t * t * (3. - 2. * t)
+}
@@ -0,0 +1,64 @@
+use crate::ConvertArrayView;
+
+use webgl_matrix::{Mat4, Matrix, ProjectionMatrix};
+
+/// Uniform block of the UI pass, mapping pixels from the top left of the screen.
+#[repr(C)]
+pub struct UiCamera {
+    pub projection: Mat4,
+}
+impl ConvertArrayView for UiCamera {}
+
+/// Points of the screen that UI elements are placed from.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Anchor {
+    TopLeft,
+    TopRight,
+    Center,
+    BottomRight,
+}
+
+/// Orthographic camera of the UI in CSS pixels, so that elements keep their size on any device.
+pub struct UiCameraController {
+    pub camera: UiCamera,
+    width: f32,
+    height: f32,
+}
+
+impl UiCameraController {
+    pub fn new() -> Self {
+        Self {
+            camera: UiCamera {
+                projection: Mat4::identity(),
+            },
+            width: 0.,
+            height: 0.,
+        }
+    }
+
+    pub fn resize(&mut self, width: f32, height: f32) {
+        if width == self.width && height == self.height {
+            return;
+        }
+        self.width = width;
+        self.height = height;
+        self.camera.projection =
+            Mat4::create_orthogonal_from_viewport(0., width, height, 0., -1., 1.);
+    }
+
+    pub fn size(&self) -> [f32; 2] {
+        [self.width, self.height]
+    }
+
+    /// Position `offset` pixels away from `anchor`. The Y axis points down.
+    pub fn anchor(&self, anchor: Anchor, offset: [f32; 2]) -> [f32; 2] {
+        let [w, h] = [self.width, self.height];
+        let [x, y] = match anchor {
+            Anchor::TopLeft => [0., 0.],
+            Anchor::TopRight => [w, 0.],
+            Anchor::Center => [w / 2., h / 2.],
+            Anchor::BottomRight => [w, h],
+        };
+        [x + offset[0], y + offset[1]]
+    }
+}
@@ -1,28 +1,51 @@
 // use super::_interfaces::{Batting, BattingState, HitInfo};
 use crate::bezier::bezier_scalar;
+use crate::configs::difficulty::DifficultyPreset;
 use crate::game_state::batting::{BatCoord, BattingConfig};
+use crate::game_state::{ContactGrade, ContactQuality, Difficulty, SwingKind};
 use crate::impls::vector::{Cross, Normalize};
 use crate::log;
 
 use std::f32::consts::PI;
 use webgl_matrix::{Mat4, Matrix, Vec3, Vector};
 
+pub struct SwingProfile {
+    duration: f32,
+    active: (f32, f32),
+    ideal: f32,
+    arm_angle_range: (f32, f32, f32, f32),
+    bat_angle_range: (f32, f32, f32, f32),
+    bat_length: f32,
+    bat_width: f32,
+    exit_velocity_scale: f32,
+    /// Releasing the button before this swing degree aborts the swing.
+    cancel_limit: f32,
+    /// How far the bat keeps going after an aborted swing, and how long it takes to stop.
+    check_overrun: (f32, f32),
+}
+
 pub struct BattingConfigImpl {
-    swing_duration: f32,
-    swing_active: (f32, f32),
+    normal_swing: SwingProfile,
+    power_swing: SwingProfile,
+    contact_swing: SwingProfile,
+    bunt: SwingProfile,
     batting_area_center: Vec3,
     batting_area_u_axis: Vec3,
     batting_area_v_axis: Vec3,
     batting_area_rect: (f32, f32),
+    aim_center: Vec3,
+    aim_rect: (f32, f32),
     arm_rot_pivot: Vec3,
     arm_rot_axis: Vec3,
-    arm_angle_range: (f32, f32, f32, f32),
     bat_rot_pivot: Vec3,
     bat_rot_axis: Vec3,
-    bat_angle_range: (f32, f32, f32, f32),
     bat_center: Vec3,
-    bat_length: f32,
-    bat_width: f32,
+    /// Scale of the bat's hitting area, set from the difficulty.
+    bat_forgiveness: f32,
+    sweet_spot: [f32; 2],
+    /// Tolerances of (timing, sweet spot) to be graded as perfect and good respectively.
+    perfect_contact: (f32, f32),
+    good_contact: (f32, f32),
 }
 
 impl BattingConfigImpl {
@@ -32,11 +55,24 @@ impl BattingConfigImpl {
         self.batting_area_u_axis = self.batting_area_u_axis.normalize();
         self.batting_area_v_axis = self.batting_area_v_axis.normalize();
     }
+
+    fn profile(&self, kind: SwingKind) -> &SwingProfile {
+        match kind {
+            SwingKind::Normal => &self.normal_swing,
+            SwingKind::Power => &self.power_swing,
+            SwingKind::Contact => &self.contact_swing,
+            SwingKind::Bunt => &self.bunt,
+        }
+    }
 }
 
 impl BattingConfig for BattingConfigImpl {
-    fn normalized_time(&self, time: f32) -> f32 {
-        time / self.swing_duration
+    fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.bat_forgiveness = DifficultyPreset::of(difficulty).bat_forgiveness;
+    }
+
+    fn normalized_time(&self, kind: SwingKind, time: f32) -> f32 {
+        time / self.profile(kind).duration
     }
 
     fn constrain_batting_area(&self, batter_position: &Vec3) -> Vec3 {
@@ -62,13 +98,29 @@ impl BattingConfig for BattingConfigImpl {
             .add(&self.batting_area_v_axis.scale(v))
     }
 
-    fn is_active_swing_time(&self, t: f32) -> bool {
-        self.swing_active.0 < t && t < self.swing_active.1
+    fn contact_plane(&self) -> (Vec3, Vec3) {
+        (self.aim_center, [0., 0., 1.])
+    }
+
+    fn aim(&self, cursor: &Vec3) -> [f32; 2] {
+        let aim = cursor.sub(&self.aim_center);
+        [
+            aim[0].clamp(-self.aim_rect.0, self.aim_rect.0),
+            aim[1].clamp(-self.aim_rect.1, self.aim_rect.1),
+        ]
+    }
+
+    fn is_active_swing_time(&self, kind: SwingKind, t: f32) -> bool {
+        let active = self.profile(kind).active;
+        active.0 < t && t < active.1
     }
 
-    fn bat_coord(&self, t: f32) -> BatCoord {
-        let arm_rotation = rotate_matrix_bezier_angle(t, &self.arm_rot_axis, self.arm_angle_range);
-        let bat_rotation = rotate_matrix_bezier_angle(t, &self.bat_rot_axis, self.bat_angle_range);
+    fn bat_coord(&self, kind: SwingKind, t: f32) -> BatCoord {
+        let profile = self.profile(kind);
+        let arm_rotation =
+            rotate_matrix_bezier_angle(t, &self.arm_rot_axis, profile.arm_angle_range);
+        let bat_rotation =
+            rotate_matrix_bezier_angle(t, &self.bat_rot_axis, profile.bat_angle_range);
 
         let hand = rotate_around(&self.bat_rot_pivot, &self.arm_rot_pivot, &arm_rotation);
         let origin = rotate_around(
@@ -93,8 +145,63 @@ impl BattingConfig for BattingConfigImpl {
         }
     }
 
-    fn is_valid_meet(&self, [x, y]: [f32; 2]) -> bool {
-        x.abs() <= self.bat_length * 0.5 && y.abs() <= self.bat_width * 0.5
+    fn is_valid_meet(&self, kind: SwingKind, [x, y]: [f32; 2]) -> bool {
+        let profile = self.profile(kind);
+        let scale = self.bat_forgiveness * 0.5;
+        x.abs() <= profile.bat_length * scale && y.abs() <= profile.bat_width * scale
+    }
+
+    fn contact_quality(&self, kind: SwingKind, t: f32, [x, y]: [f32; 2]) -> ContactQuality {
+        let profile = self.profile(kind);
+        // The bat is short of the ideal point when the swing is late.
+        let timing = profile.ideal - t;
+        let scale = self.bat_forgiveness * 0.5;
+        // distance of the meet position from the sweet spot, 1 at the edge of the bat
+        let sweet_spot = [
+            (x - self.sweet_spot[0]) / (profile.bat_length * scale),
+            (y - self.sweet_spot[1]) / (profile.bat_width * scale),
+            0.,
+        ]
+        .mag();
+
+        let grade =
+            if timing.abs() <= self.perfect_contact.0 && sweet_spot <= self.perfect_contact.1 {
+                ContactGrade::Perfect
+            } else if timing < -self.good_contact.0 {
+                ContactGrade::Early
+            } else if timing > self.good_contact.0 {
+                ContactGrade::Late
+            } else if sweet_spot > self.good_contact.1 {
+                ContactGrade::Weak
+            } else {
+                ContactGrade::Good
+            };
+
+        ContactQuality {
+            timing: timing * profile.duration,
+            grade,
+        }
+    }
+
+    fn exit_velocity_scale(&self, kind: SwingKind) -> f32 {
+        self.profile(kind).exit_velocity_scale
+    }
+
+    fn cancel_limit(&self, kind: SwingKind) -> f32 {
+        self.profile(kind).cancel_limit
+    }
+
+    fn checked_degree(&self, kind: SwingKind, released_t: f32, time: f32) -> Option<f32> {
+        let (overrun, duration) = self.profile(kind).check_overrun;
+        let k = time / duration;
+        if k < 1. {
+            Some(released_t + overrun * (1. - (1. - k) * (1. - k)))
+        } else if k < 2. {
+            // the bat is pulled back to the stance
+            Some((released_t + overrun) * (2. - k))
+        } else {
+            None
+        }
     }
 }
 
@@ -116,21 +223,93 @@ fn rotate_around(point: &Vec3, origin: &Vec3, mat: &Mat4) -> Vec3 {
 impl BattingConfigImpl {
     pub fn default() -> Self {
         Self {
-            swing_duration: 400.,
+            normal_swing: SwingProfile {
+                duration: 400.,
+                active: (0.3, 0.8),
+                ideal: 0.55,
+                arm_angle_range: (0., 0.333 * PI, 0.666 * PI, PI),
+                bat_angle_range: (0., 0.2 * PI, 0.4 * PI, 0.6 * PI),
+                bat_length: 0.5,
+                bat_width: 0.2,
+                exit_velocity_scale: 1.,
+                cancel_limit: 0.25,
+                check_overrun: (0.15, 120.),
+            },
+            power_swing: SwingProfile {
+                duration: 480.,
+                active: (0.4, 0.75),
+                ideal: 0.57,
+                arm_angle_range: (0., 0.35 * PI, 0.7 * PI, 1.05 * PI),
+                bat_angle_range: (0., 0.25 * PI, 0.45 * PI, 0.65 * PI),
+                bat_length: 0.45,
+                bat_width: 0.15,
+                exit_velocity_scale: 1.3,
+                cancel_limit: 0.2,
+                check_overrun: (0.2, 140.),
+            },
+            contact_swing: SwingProfile {
+                duration: 340.,
+                active: (0.25, 0.85),
+                ideal: 0.55,
+                arm_angle_range: (0., 0.3 * PI, 0.6 * PI, 0.9 * PI),
+                bat_angle_range: (0., 0.2 * PI, 0.4 * PI, 0.55 * PI),
+                bat_length: 0.55,
+                bat_width: 0.26,
+                exit_velocity_scale: 0.8,
+                cancel_limit: 0.3,
+                check_overrun: (0.1, 100.),
+            },
+            // The bat is held out over the plate instead of being swung.
+            bunt: SwingProfile {
+                duration: 1200.,
+                active: (0.05, 0.95),
+                ideal: 0.5,
+                arm_angle_range: (0.5 * PI, 0.5 * PI, 0.5 * PI, 0.5 * PI),
+                bat_angle_range: (0.3 * PI, 0.3 * PI, 0.3 * PI, 0.3 * PI),
+                bat_length: 0.6,
+                bat_width: 0.3,
+                exit_velocity_scale: 0.25,
+                cancel_limit: 1.,
+                check_overrun: (0., 100.),
+            },
             batting_area_center: [0., 0., 0.8],
             batting_area_rect: (0.3, 0.5),
             batting_area_u_axis: [1., 0., 0.],
             batting_area_v_axis: [0., 0., 1.],
-            swing_active: (0.3, 0.8),
+            aim_center: [0., -0.5, 0.8],
+            aim_rect: (0.3, 0.3),
             arm_rot_pivot: [0., 1., 0.],
             arm_rot_axis: [0., 1., 1.],
-            arm_angle_range: (0., 0.333 * PI, 0.666 * PI, PI),
             bat_rot_pivot: [0., 1., -0.2],
             bat_rot_axis: [1., 0., 0.],
-            bat_angle_range: (0., 0.2 * PI, 0.4 * PI, 0.6 * PI),
             bat_center: [0., 1.5, -0.2],
-            bat_length: 0.5,
-            bat_width: 0.2,
+            bat_forgiveness: 1.,
+            sweet_spot: [0.1, 0.],
+            perfect_contact: (0.04, 0.3),
+            good_contact: (0.12, 0.7),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swing_behind_the_ball_is_late() {
+        let config = BattingConfigImpl::default();
+        let ideal = config.normal_swing.ideal;
+        let sweet_spot = config.sweet_spot;
+
+        let late = config.contact_quality(SwingKind::Normal, ideal - 0.2, sweet_spot);
+        assert!(late.grade == ContactGrade::Late);
+        assert!(late.timing > 0.);
+
+        let early = config.contact_quality(SwingKind::Normal, ideal + 0.2, sweet_spot);
+        assert!(early.grade == ContactGrade::Early);
+        assert!(early.timing < 0.);
+
+        let perfect = config.contact_quality(SwingKind::Normal, ideal, sweet_spot);
+        assert!(perfect.grade == ContactGrade::Perfect);
+    }
+}
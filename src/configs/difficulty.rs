@@ -0,0 +1,55 @@
+use crate::game_state::{Difficulty, PitchKind};
+
+#[derive(Clone, Copy)]
+pub struct DifficultyPreset {
+    /// Multiplier of the ball speed.
+    pub pitch_speed: f32,
+    pub break_scale: f32,
+    pub pitch_mix: [(PitchKind, f32); 4],
+    pub control_error: f32,
+    /// Multiplier of the effective bat size.
+    pub bat_forgiveness: f32,
+}
+
+impl DifficultyPreset {
+    pub fn of(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => Self {
+                pitch_speed: 0.8,
+                break_scale: 0.5,
+                pitch_mix: [
+                    (PitchKind::Fastball, 0.7),
+                    (PitchKind::Curveball, 0.1),
+                    (PitchKind::Slider, 0.1),
+                    (PitchKind::Changeup, 0.1),
+                ],
+                control_error: 0.03,
+                bat_forgiveness: 1.3,
+            },
+            Difficulty::Normal => Self {
+                pitch_speed: 1.,
+                break_scale: 1.,
+                pitch_mix: [
+                    (PitchKind::Fastball, 0.5),
+                    (PitchKind::Curveball, 0.15),
+                    (PitchKind::Slider, 0.2),
+                    (PitchKind::Changeup, 0.15),
+                ],
+                control_error: 0.05,
+                bat_forgiveness: 1.,
+            },
+            Difficulty::Hard => Self {
+                pitch_speed: 1.2,
+                break_scale: 1.3,
+                pitch_mix: [
+                    (PitchKind::Fastball, 0.35),
+                    (PitchKind::Curveball, 0.25),
+                    (PitchKind::Slider, 0.25),
+                    (PitchKind::Changeup, 0.15),
+                ],
+                control_error: 0.04,
+                bat_forgiveness: 0.8,
+            },
+        }
+    }
+}
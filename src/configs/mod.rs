@@ -1,2 +1,5 @@
 pub mod batting;
+pub mod difficulty;
+pub mod pitcher_ai;
 pub mod pitching;
+pub mod umpire;
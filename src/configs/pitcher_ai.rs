@@ -0,0 +1,50 @@
+use crate::configs::difficulty::DifficultyPreset;
+use crate::game_state::pitcher_ai::PitcherAiConfig;
+use crate::game_state::{Count, Difficulty, PitchKind};
+
+pub struct PitcherAiConfigImpl {
+    difficulty: DifficultyPreset,
+    zone_rate: f32,
+    zone_half_size: [f32; 2],
+    memory: usize,
+}
+
+impl PitcherAiConfig for PitcherAiConfigImpl {
+    fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = DifficultyPreset::of(difficulty);
+    }
+
+    fn pitch_mix(&self) -> &[(PitchKind, f32)] {
+        &self.difficulty.pitch_mix
+    }
+
+    fn zone_rate(&self, count: &Count) -> f32 {
+        let lead = count.balls as f32 - count.strikes as f32;
+        (self.zone_rate + 0.15 * lead).clamp(0.05, 0.95)
+    }
+
+    fn zone_half_size(&self) -> [f32; 2] {
+        self.zone_half_size
+    }
+
+    fn control_error(&self) -> f32 {
+        self.difficulty.control_error
+    }
+
+    fn memory(&self) -> usize {
+        self.memory
+    }
+}
+
+// Concrete Instances
+
+impl PitcherAiConfigImpl {
+    pub fn default() -> Self {
+        Self {
+            difficulty: DifficultyPreset::of(Difficulty::Normal),
+            zone_rate: 0.55,
+            zone_half_size: [0.25, 0.3],
+            memory: 6,
+        }
+    }
+}
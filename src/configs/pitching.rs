@@ -1,22 +1,54 @@
-use crate::bezier::ParametricCurveSequence;
+use crate::bezier::{BezierSequence, Curve, ParametricCurveSequence};
+use crate::configs::difficulty::DifficultyPreset;
 use crate::game_state::pitching::PitchingConfig;
+use crate::game_state::{Difficulty, Pitch, PitchKind};
 use crate::log;
 
-use webgl_matrix::Vec3;
+use webgl_matrix::{Vec3, Vector};
 
-pub struct PitchingConfigImpl<C> {
-    curve: C,
+pub struct PitchType {
+    kind: PitchKind,
+    /// Time for the ball to travel from the release point to the end of the path.
+    ball_duration: f32,
+    /// Speed shown to the player at the usual speed, in miles per hour.
+    speed: f32,
+    /// Offset from a straight path at the plate.
+    break_: [f32; 2],
+}
+
+pub struct PitchingConfigImpl {
     idle_duration: f32,
     pitching_duration: f32,
     throws_at: f32,
-    ball_duration: f32,
     pitcher_position: Vec3,
+    release_point: Vec3,
+    zone_center: Vec3,
+    /// Depth where the ball goes out of sight after passing the plate.
+    end_depth: f32,
+    pitch_types: Vec<PitchType>,
+    /// Multipliers set from the difficulty.
+    pitch_speed: f32,
+    break_scale: f32,
 }
 
-impl<C> PitchingConfig for PitchingConfigImpl<C>
-where
-    C: ParametricCurveSequence,
-{
+impl PitchingConfigImpl {
+    fn pitch_type(&self, kind: PitchKind) -> &PitchType {
+        self.pitch_types
+            .iter()
+            .find(|pitch_type| pitch_type.kind == kind)
+            .unwrap_or(&self.pitch_types[0])
+    }
+}
+
+impl PitchingConfig for PitchingConfigImpl {
+    type Curve = BezierSequence;
+
+    fn set_difficulty(&mut self, difficulty: Difficulty) {
+        let preset = DifficultyPreset::of(difficulty);
+        self.pitch_speed = preset.pitch_speed;
+        self.break_scale = preset.break_scale;
+    }
+
     fn next_idle_break(&self, offset: f32, timestamp: f32) -> f32 {
         let duration = self.idle_duration;
         ((timestamp - offset) / duration).ceil() * duration + offset
@@ -39,34 +71,92 @@ where
         time / self.pitching_duration
     }
 
-    fn ball_parameter(&self, time: f32) -> f32 {
-        (time - self.throws_at) / self.ball_duration
+    fn pitcher_position(&self) -> Vec3 {
+        self.pitcher_position
+    }
+
+    fn ball_parameter(&self, pitch: &Pitch, time: f32) -> f32 {
+        (time - self.throws_at) * self.pitch_speed * pitch.speed
+            / self.pitch_type(pitch.kind).ball_duration
+    }
+
+    fn speed(&self, pitch: &Pitch) -> f32 {
+        self.pitch_type(pitch.kind).speed * self.pitch_speed * pitch.speed
+    }
+
+    fn curve(&self, pitch: &Pitch) -> BezierSequence {
+        let pitch_type = self.pitch_type(pitch.kind);
+        let p_0 = self.release_point;
+        // parameter where the ball crosses the plate
+        let f = (self.zone_center[2] - p_0[2]) / (self.end_depth - p_0[2]);
+        let break_ = [pitch_type.break_[0], pitch_type.break_[1], 0.].scale(self.break_scale);
+
+        // Only the last control point is shifted by the break so that the ball leaves the
+        // straight path slowly at first and sharply toward the plate. The shift at the plate is
+        // `f^3` of that of the last control point.
+        let target = self
+            .zone_center
+            .add(&[pitch.target[0], pitch.target[1], 0.])
+            .sub(&break_);
+        let p_3 = p_0.add(&target.sub(&p_0).scale(1. / f));
+        let step = p_3.sub(&p_0).scale(1. / 3.);
+
+        BezierSequence::new(vec![Curve {
+            t_duration: 1.,
+            t_p: None,
+            p_0,
+            p_1: p_0.add(&step),
+            p_2: p_0.add(&step.scale(2.)),
+            p_3: p_3.add(&break_.scale(1. / (f * f * f))),
+        }])
     }
 
-    fn ball_position(&self, t: f32) -> Option<Vec3> {
+    fn ball_position(&self, curve: &BezierSequence, t: f32) -> Option<Vec3> {
         // log::log_f32(t);
-        self.curve.calc_point(self.curve.duration() * t, false)
+        curve.calc_point(curve.duration() * t, false)
     }
 }
 
-use crate::bezier::{BezierSequence, Curve};
+// Concrete Instances
 
-impl PitchingConfigImpl<BezierSequence> {
+impl PitchingConfigImpl {
     pub fn default() -> Self {
         Self {
-            curve: BezierSequence::new(vec![Curve {
-                t_duration: 333.0,
-                t_p: None,
-                p_0: [0., -0.6, 2.],
-                p_1: [0., -0.6, 3.],
-                p_2: [0., -0.6, 4.],
-                p_3: [0., -0.6, 5.],
-            }]),
             idle_duration: 300.,
             pitching_duration: 500.,
             throws_at: 200.,
-            ball_duration: 1000.,
-            pitcher_position: [0., 0., 2.],
+            pitcher_position: [0., -0.8, -5.],
+            release_point: [0.1, -0.2, -5.],
+            zone_center: [0., -0.5, 0.8],
+            end_depth: 2.,
+            pitch_types: vec![
+                PitchType {
+                    kind: PitchKind::Fastball,
+                    ball_duration: 700.,
+                    speed: 94.,
+                    break_: [0., 0.05],
+                },
+                PitchType {
+                    kind: PitchKind::Curveball,
+                    ball_duration: 950.,
+                    speed: 78.,
+                    break_: [0.1, -0.4],
+                },
+                PitchType {
+                    kind: PitchKind::Slider,
+                    ball_duration: 800.,
+                    speed: 85.,
+                    break_: [-0.35, -0.1],
+                },
+                PitchType {
+                    kind: PitchKind::Changeup,
+                    ball_duration: 900.,
+                    speed: 83.,
+                    break_: [0.05, -0.2],
+                },
+            ],
+            pitch_speed: 1.,
+            break_scale: 1.,
         }
     }
 }
@@ -0,0 +1,54 @@
+use crate::game_state::umpire::UmpireConfig;
+use crate::game_state::SwingAttempt;
+
+use webgl_matrix::Vec3;
+
+pub struct UmpireConfigImpl {
+    zone_center: Vec3,
+    zone_half_size: [f32; 2],
+    /// A checked swing whose bat went beyond this swing degree is called as a swing.
+    check_swing_limit: f32,
+    balls_for_walk: u32,
+    strikes_for_out: u32,
+}
+
+impl UmpireConfig for UmpireConfigImpl {
+    fn plate_depth(&self) -> f32 {
+        self.zone_center[2]
+    }
+
+    fn is_in_zone(&self, position: &Vec3) -> bool {
+        (position[0] - self.zone_center[0]).abs() <= self.zone_half_size[0]
+            && (position[1] - self.zone_center[1]).abs() <= self.zone_half_size[1]
+    }
+
+    fn is_swing(&self, attempt: SwingAttempt) -> bool {
+        match attempt {
+            SwingAttempt::NoSwing => false,
+            SwingAttempt::Checked(swing_degree) => swing_degree >= self.check_swing_limit,
+            SwingAttempt::Full => true,
+        }
+    }
+
+    fn balls_for_walk(&self) -> u32 {
+        self.balls_for_walk
+    }
+
+    fn strikes_for_out(&self) -> u32 {
+        self.strikes_for_out
+    }
+}
+
+// Concrete Instances
+
+impl UmpireConfigImpl {
+    pub fn default() -> Self {
+        Self {
+            zone_center: [0., -0.5, 0.8],
+            zone_half_size: [0.25, 0.3],
+            check_swing_limit: 0.4,
+            balls_for_walk: 4,
+            strikes_for_out: 3,
+        }
+    }
+}
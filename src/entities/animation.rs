@@ -0,0 +1,259 @@
+use super::Frame;
+use crate::shaders::entity_shader::{Billboard, Instance};
+
+use webgl_matrix::Mat4;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PlayMode {
+    Loop,
+    /// Stays on the last frame once played through.
+    Once,
+    /// Plays forward and backward alternately.
+    PingPong,
+}
+
+#[derive(Clone)]
+pub struct Keyframe {
+    pub frame: Frame,
+    pub duration: f32,
+    /// Fired when the frame is entered.
+    pub event: Option<&'static str>,
+}
+
+pub struct Clip {
+    pub name: &'static str,
+    pub mode: PlayMode,
+    pub frames: Vec<Keyframe>,
+}
+
+struct Blend {
+    from: usize,
+    from_frame: usize,
+    started_at: f32,
+}
+
+/// Plays the clips of an entity. The owner tells which clip to show and how far it has gone each
+/// frame, so that a clip can follow either the clock or a parameter such as the swing degree.
+pub struct Animator {
+    clips: Vec<Clip>,
+    /// Milliseconds taken to cross-fade into a new clip.
+    blend_duration: f32,
+    current: usize,
+    current_frame: usize,
+    /// Whether any clip has been shown yet.
+    is_started: bool,
+    blend: Option<Blend>,
+    time: f32,
+}
+
+impl Keyframe {
+    pub fn new(frame: Frame, duration: f32) -> Self {
+        Self {
+            frame,
+            duration,
+            event: None,
+        }
+    }
+
+    pub fn with_event(mut self, event: &'static str) -> Self {
+        self.event = Some(event);
+        self
+    }
+}
+
+impl Clip {
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|keyframe| keyframe.duration).sum()
+    }
+
+    /// Index of the frame shown after `elapsed` since the clip started.
+    pub fn frame_index(&self, elapsed: f32) -> usize {
+        let duration = self.duration();
+        let last = self.frames.len().saturating_sub(1);
+        if duration <= 0. {
+            return 0;
+        }
+        let elapsed = elapsed.max(0.);
+        let local = match self.mode {
+            PlayMode::Loop => elapsed % duration,
+            PlayMode::Once if elapsed >= duration => return last,
+            PlayMode::Once => elapsed,
+            PlayMode::PingPong => {
+                let local = elapsed % (duration * 2.);
+                if local < duration {
+                    local
+                } else {
+                    duration * 2. - local
+                }
+            }
+        };
+        let mut end = 0.;
+        for (index, keyframe) in self.frames.iter().enumerate() {
+            end += keyframe.duration;
+            if local < end {
+                return index;
+            }
+        }
+        last
+    }
+}
+
+impl Animator {
+    pub fn new(clips: Vec<Clip>, blend_duration: f32) -> Self {
+        Self {
+            clips,
+            blend_duration,
+            current: 0,
+            current_frame: 0,
+            is_started: false,
+            blend: None,
+            time: 0.,
+        }
+    }
+
+    fn clip_index(&self, name: &str) -> Option<usize> {
+        self.clips.iter().position(|clip| clip.name == name)
+    }
+
+    /// Shows `clip` at `elapsed` and returns the events of the frames entered since the last
+    /// update. Unknown clips are ignored.
+    pub fn update(&mut self, time: f32, clip: &str, elapsed: f32) -> Vec<&'static str> {
+        let index = match self.clip_index(clip) {
+            Some(index) => index,
+            None => return vec![],
+        };
+        let frame = self.clips[index].frame_index(elapsed);
+        let entered = self.entered_frames(index, frame);
+        // nothing has been shown to fade from before the first clip
+        if self.is_started && index != self.current && self.blend_duration > 0. {
+            self.blend = Some(Blend {
+                from: self.current,
+                from_frame: self.current_frame,
+                started_at: time,
+            });
+        }
+        self.current = index;
+        self.current_frame = frame;
+        self.is_started = true;
+        self.time = time;
+
+        let frames = &self.clips[index].frames;
+        entered
+            .into_iter()
+            .filter_map(|frame| frames[frame].event)
+            .collect()
+    }
+
+    /// Frames passed through to reach `frame` of `clip` from the current frame, in order. A clip
+    /// that has just started is entered from its first frame.
+    fn entered_frames(&self, clip: usize, frame: usize) -> Vec<usize> {
+        if !self.is_started || clip != self.current {
+            return (0..=frame).collect();
+        }
+        let current = self.current_frame;
+        if frame >= current {
+            return (current + 1..=frame).collect();
+        }
+        match self.clips[clip].mode {
+            PlayMode::Loop => {
+                let len = self.clips[clip].frames.len();
+                (current + 1..len).chain(0..=frame).collect()
+            }
+            PlayMode::PingPong => (frame..current).rev().collect(),
+            // played again from the start
+            PlayMode::Once => (0..=frame).collect(),
+        }
+    }
+
+    fn keyframe(&self, clip: usize, frame: usize) -> Option<&Keyframe> {
+        self.clips.get(clip)?.frames.get(frame)
+    }
+
+    /// Instances to draw for the current frame. The frame of the previous clip is drawn as well
+    /// while cross-fading.
+    pub fn instances(&self, model: Mat4) -> Vec<Instance> {
+        let weight = match &self.blend {
+            Some(blend) => ((self.time - blend.started_at) / self.blend_duration).clamp(0., 1.),
+            None => 1.,
+        };
+        let mut instances = vec![];
+        if let Some(blend) = self.blend.as_ref().filter(|_| weight < 1.) {
+            if let Some(keyframe) = self.keyframe(blend.from, blend.from_frame) {
+                instances.push(instance(&keyframe.frame, model, 1. - weight));
+            }
+        }
+        if let Some(keyframe) = self.keyframe(self.current, self.current_frame) {
+            instances.push(instance(&keyframe.frame, model, weight));
+        }
+        instances
+    }
+}
+
+fn instance(frame: &Frame, model: Mat4, opacity: f32) -> Instance {
+    Instance {
+        model,
+        uv_offset: frame.uv_offset,
+        uv_scale: frame.uv_scale,
+        pos_offset: frame.pos_offset,
+        opacity,
+        billboard: Billboard::None.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use webgl_matrix::Matrix;
+
+    fn keyframe(event: &'static str) -> Keyframe {
+        let frame = Frame {
+            uv_offset: [0., 0.],
+            uv_scale: [1., 1.],
+            pos_offset: [0., 0.],
+        };
+        Keyframe::new(frame, 0.25).with_event(event)
+    }
+
+    fn clip(name: &'static str, mode: PlayMode) -> Clip {
+        Clip {
+            name,
+            mode,
+            frames: vec![keyframe("a"), keyframe("b"), keyframe("c"), keyframe("d")],
+        }
+    }
+
+    #[test]
+    fn events_of_skipped_frames_are_returned_in_order() {
+        let mut animator = Animator::new(
+            vec![clip("once", PlayMode::Once), clip("loop", PlayMode::Loop)],
+            0.,
+        );
+        assert_eq!(animator.update(0., "once", 0.6), vec!["a", "b", "c"]);
+        assert!(animator.update(1., "once", 0.7).is_empty());
+        assert_eq!(animator.update(2., "once", 1.), vec!["d"]);
+
+        assert_eq!(animator.update(3., "loop", 0.8), vec!["a", "b", "c", "d"]);
+        assert_eq!(animator.update(4., "loop", 1.3), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn first_clip_is_shown_without_blending() {
+        let mut animator = Animator::new(
+            vec![clip("once", PlayMode::Once), clip("loop", PlayMode::Loop)],
+            100.,
+        );
+        animator.update(0., "loop", 0.);
+        let instances = animator.instances(Mat4::identity());
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].opacity, 1.);
+
+        // later changes of clip still cross-fade
+        animator.update(50., "once", 0.);
+        let opacities = animator
+            .instances(Mat4::identity())
+            .iter()
+            .map(|instance| instance.opacity)
+            .collect::<Vec<_>>();
+        assert_eq!(opacities, vec![1., 0.]);
+    }
+}
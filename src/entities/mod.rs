@@ -1,7 +1,10 @@
+pub mod animation;
 pub mod sample_batter;
 pub mod thrown_ball;
 
-use crate::shaders::entity_shader::Instance;
+use crate::shader::{Atlas, AtlasFrame};
+use crate::shaders::entity_shader::{Billboard, Instance};
+use animation::Animator;
 
 use webgl_matrix::Mat4;
 
@@ -12,45 +15,39 @@ pub struct Frame {
     pos_offset: [f32; 2],
 }
 
-pub trait Renderable {
-    const FRAMES: [Option<(f32, Frame)>; 16];
+impl Frame {
+    pub fn from_atlas(atlas: &Atlas, name: &str) -> Result<Self, String> {
+        Ok(Self::from(&atlas.frame(name)?))
+    }
+}
+
+impl From<&AtlasFrame> for Frame {
+    fn from(frame: &AtlasFrame) -> Self {
+        Self {
+            uv_offset: frame.uv_offset,
+            uv_scale: frame.uv_scale,
+            pos_offset: frame.pivot_offset,
+        }
+    }
+}
 
+pub trait Renderable {
     fn model(&self) -> Mat4;
     fn set_model(&mut self, model: Mat4);
+    fn animator(&self) -> &Animator;
+    fn billboard(&self) -> Billboard {
+        Billboard::None
+    }
 }
 
-pub fn get_current_instance_value<T>(target: &T, t: f32) -> Instance
+pub fn get_current_instances<T>(target: &T) -> Vec<Instance>
 where
     T: Renderable,
 {
-    let frames = T::FRAMES;
-    let mut frames = frames.iter();
-    let mut frame: Option<Frame> = None;
-    while let Some(Some((threshold, curr))) = frames.next() {
-        if t < *threshold {
-            frame = Some(curr.clone());
-            break;
-        }
-    }
-
-    let Frame {
-        uv_offset,
-        uv_scale,
-        pos_offset,
-    } = frame.unwrap_or_else(|| {
-        T::FRAMES
-            .get(0)
-            .as_ref()
-            .unwrap()
-            .as_ref()
-            .unwrap()
-            .1
-            .clone()
-    });
-    Instance {
-        model: target.model(),
-        uv_offset,
-        uv_scale,
-        pos_offset,
+    let billboard = target.billboard().into();
+    let mut instances = target.animator().instances(target.model());
+    for instance in &mut instances {
+        instance.billboard = billboard;
     }
+    instances
 }
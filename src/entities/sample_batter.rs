@@ -1,79 +1,78 @@
+use super::animation::{Animator, Clip, Keyframe, PlayMode};
 use super::{Frame, Renderable};
+use crate::shader::Atlas;
+use crate::shaders::entity_shader::Billboard;
 
 use webgl_matrix::{Mat4, Matrix};
 
 pub struct SampleEntity {
-    start_at: f32,
     model: Mat4,
+    animator: Animator,
 }
 
-const HEIGHT: f32 = 0.2197265625;
+/// Swing degree where the follow-through begins.
+const FOLLOW_THROUGH_AT: f32 = 0.6;
 
 impl SampleEntity {
-    pub fn new() -> Self {
-        Self {
-            start_at: f32::MAX,
+    pub fn new(atlas: &Atlas) -> Result<Self, String> {
+        let frame = |name: &str| Frame::from_atlas(atlas, name);
+        Ok(Self {
             model: Mat4::identity(),
-        }
+            animator: Animator::new(
+                vec![
+                    Clip {
+                        name: "idle",
+                        mode: PlayMode::PingPong,
+                        frames: vec![Keyframe::new(frame("batter_0")?, 1000.)],
+                    },
+                    // played by the swing degree
+                    Clip {
+                        name: "swing",
+                        mode: PlayMode::Once,
+                        frames: vec![
+                            Keyframe::new(frame("batter_0")?, 0.2),
+                            Keyframe::new(frame("batter_1")?, 0.2),
+                            Keyframe::new(frame("batter_2")?, 0.2).with_event("bat_contact"),
+                        ],
+                    },
+                    Clip {
+                        name: "follow_through",
+                        mode: PlayMode::Once,
+                        frames: vec![Keyframe::new(frame("batter_3")?, 0.4)],
+                    },
+                ],
+                80.,
+            ),
+        })
     }
 
-    pub fn start(&mut self, time: f32) {
-        self.start_at = time;
+    /// Shows the pose of the swing degree, or the idle pose if not swinging.
+    pub fn pose(&mut self, time: f32, swing_degree: Option<f32>) -> Vec<&'static str> {
+        match swing_degree {
+            None => self.animator.update(time, "idle", time),
+            Some(degree) if degree < FOLLOW_THROUGH_AT => {
+                self.animator.update(time, "swing", degree)
+            }
+            Some(degree) => {
+                self.animator
+                    .update(time, "follow_through", degree - FOLLOW_THROUGH_AT)
+            }
+        }
     }
 }
 
-/// sample_entity_0.png
+/// entities0.png
 impl Renderable for SampleEntity {
-    const FRAMES: [Option<(f32, Frame)>; 16] = [
-        Some((
-            0.2,
-            Frame {
-                uv_offset: [0.01, 1. - HEIGHT],
-                uv_scale: [0.126953125, HEIGHT],
-                pos_offset: [0.1, 0.],
-            },
-        )),
-        Some((
-            0.4,
-            Frame {
-                uv_offset: [0.13916015625, 1. - HEIGHT],
-                uv_scale: [0.126953125, HEIGHT],
-                pos_offset: [-0.1, 0.],
-            },
-        )),
-        Some((
-            0.6,
-            Frame {
-                uv_offset: [0.4345703125, 1. - HEIGHT],
-                uv_scale: [0.2099609375, HEIGHT],
-                pos_offset: [0.6, 0.],
-            },
-        )),
-        Some((
-            3.0,
-            Frame {
-                uv_offset: [0.27099609375, 1. - HEIGHT],
-                uv_scale: [0.1611328125, HEIGHT],
-                pos_offset: [-0.3, 0.],
-            },
-        )),
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-    ];
     fn model(&self) -> Mat4 {
         self.model
     }
     fn set_model(&mut self, model: Mat4) {
         self.model = model;
     }
+    fn animator(&self) -> &Animator {
+        &self.animator
+    }
+    fn billboard(&self) -> Billboard {
+        Billboard::Cylindrical
+    }
 }
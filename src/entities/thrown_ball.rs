@@ -1,49 +1,42 @@
+use super::animation::{Animator, Clip, Keyframe, PlayMode};
 use super::{Frame, Renderable};
+use crate::shader::Atlas;
+use crate::shaders::entity_shader::Billboard;
 
 use webgl_matrix::{Mat4, Matrix};
 
 pub struct ThrownBall {
     model: Mat4,
+    animator: Animator,
 }
 
 impl ThrownBall {
-    pub fn new() -> Self {
-        Self {
+    pub fn new(atlas: &Atlas) -> Result<Self, String> {
+        Ok(Self {
             model: Mat4::identity(),
-        }
+            animator: Animator::new(
+                vec![Clip {
+                    name: "default",
+                    mode: PlayMode::Loop,
+                    frames: vec![Keyframe::new(Frame::from_atlas(atlas, "ball")?, 1.)],
+                }],
+                0.,
+            ),
+        })
     }
 }
 
 impl Renderable for ThrownBall {
-    const FRAMES: [Option<(f32, Frame)>; 16] = [
-        Some((
-            1.,
-            Frame {
-                uv_offset: [0., 0.],
-                uv_scale: [0.05, 0.05],
-                pos_offset: [0., 0.],
-            },
-        )),
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-    ];
     fn model(&self) -> Mat4 {
         self.model
     }
     fn set_model(&mut self, model: Mat4) {
         self.model = model;
     }
+    fn animator(&self) -> &Animator {
+        &self.animator
+    }
+    fn billboard(&self) -> Billboard {
+        Billboard::Spherical
+    }
 }
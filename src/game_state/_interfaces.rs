@@ -8,10 +8,103 @@ pub struct HitInfo {
     pub z_axis: Vec3,
     pub origin: Vec3,
     pub meet_position: [f32; 2],
+    pub position: Vec3,
+    pub quality: ContactQuality,
+    pub exit_velocity_scale: f32,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ContactGrade {
+    Perfect,
+    Good,
+    Early,
+    Late,
+    Weak,
+}
+
+impl ContactGrade {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContactGrade::Perfect => "PERFECT",
+            ContactGrade::Good => "GOOD",
+            ContactGrade::Early => "EARLY",
+            ContactGrade::Late => "LATE",
+            ContactGrade::Weak => "WEAK",
+        }
+    }
+
+    /// Multiplier applied to the exit velocity of the batted ball.
+    pub fn power(&self) -> f32 {
+        match self {
+            ContactGrade::Perfect => 1.,
+            ContactGrade::Good => 0.85,
+            ContactGrade::Early | ContactGrade::Late => 0.7,
+            ContactGrade::Weak => 0.5,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ContactQuality {
+    /// Swing timing error in milliseconds, negative when the swing was early and positive when
+    /// it was late, i.e. when the bat had not reached the ideal point as the ball arrived.
+    pub timing: f32,
+    pub grade: ContactGrade,
+}
+
+#[derive(Clone)]
+pub struct Trajectory {
+    /// Initial speed of the ball per `HitBall` duration.
+    pub exit_velocity: f32,
+    /// Initial elevation of the ball in radians.
+    pub launch_angle: f32,
+    /// Highest point of the flight, reached `apex_time` milliseconds after the hit. It is the
+    /// start of the flight if the ball leaves the bat downward.
+    pub apex: Vec3,
+    pub apex_time: f32,
+    pub hang_time: f32,
+    pub landing: Vec3,
+    pub distance: f32,
+    pub result: HitResult,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
 }
 
 // Traits
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum PitchKind {
+    Fastball,
+    Curveball,
+    Slider,
+    Changeup,
+}
+
+impl PitchKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PitchKind::Fastball => "FASTBALL",
+            PitchKind::Curveball => "CURVEBALL",
+            PitchKind::Slider => "SLIDER",
+            PitchKind::Changeup => "CHANGEUP",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Pitch {
+    pub kind: PitchKind,
+    /// Where the ball crosses the plate, relative to the center of the strike zone.
+    pub target: [f32; 2],
+    /// Multiplier of the usual speed of the pitch type.
+    pub speed: f32,
+}
+
 pub enum PitcherState {
     Idle(f32),
     Pitching(f32),
@@ -19,6 +112,7 @@ pub enum PitcherState {
 
 pub struct PitchingState {
     pub pitcher: PitcherState,
+    pub pitcher_position: Vec3,
     pub ball_position: Option<Vec3>,
 }
 
@@ -26,22 +120,64 @@ pub trait Pitching {
     type Config;
     fn new(config: Self::Config) -> Self;
     fn reset_idle(&mut self, timestamp: f32);
-    fn pitch(&mut self, timestamp: f32);
+    fn set_difficulty(&mut self, difficulty: Difficulty);
+    fn pitch(&mut self, timestamp: f32, pitch: Pitch);
+    fn is_pitching(&self) -> bool;
+    /// Speed of `pitch` in miles per hour, as shown to the player.
+    fn speed(&self, pitch: &Pitch) -> f32;
     fn end(&mut self);
     fn update(&mut self, time: f32) -> PitchingState;
+    /// `samples` points along the path of the last pitch, from the release to where the ball goes
+    /// out of sight.
+    fn path(&self, samples: usize) -> Vec<Vec3>;
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SwingKind {
+    Normal,
+    Power,
+    Contact,
+    Bunt,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SwingAttempt {
+    NoSwing,
+    /// The swing was aborted and the bat stopped at the given swing degree.
+    Checked(f32),
+    Full,
 }
 
 pub enum BattingState {
-    Idle { batter: Vec3 },
-    Swinging { batter: Vec3, swing_degree: f32 },
-    Hit(HitInfo),
+    Idle {
+        batter: Vec3,
+    },
+    Swinging {
+        batter: Vec3,
+        swing_kind: SwingKind,
+        swing_degree: f32,
+    },
+    Hit {
+        batter: Vec3,
+        swing_kind: SwingKind,
+        swing_degree: f32,
+        info: HitInfo,
+    },
 }
 
 pub trait Batting {
     type Config;
     fn new(config: Self::Config) -> Self;
+    fn set_difficulty(&mut self, difficulty: Difficulty);
     fn set_batter_position(&mut self, position: Vec3);
-    fn swing(&mut self, timestamp: f32);
+    /// Returns a point and a normal of the plane the contact cursor moves on.
+    fn contact_plane(&self) -> (Vec3, Vec3);
+    fn set_contact_cursor(&mut self, cursor: Vec3);
+    fn contact_cursor(&self) -> Vec3;
+    fn swing(&mut self, timestamp: f32, kind: SwingKind);
+    fn release(&mut self, timestamp: f32);
+    /// Returns how far the batter went for the current pitch and resets it for the next one.
+    fn take_attempt(&mut self) -> SwingAttempt;
     // TODO: fix `get_ball_position`
     fn update(&mut self, time: f32, ball_position: Option<Vec3>) -> BattingState;
 }
@@ -58,7 +194,7 @@ pub enum HitBallState {
     },
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub enum HitResult {
     Foul,
     HomeRun,
@@ -69,5 +205,67 @@ pub trait HitBall {
     type Config;
     fn new(config: Self::Config) -> Self;
     fn hit(&mut self, timestamp: f32, info: HitInfo);
+    fn trajectory(&self) -> Option<&Trajectory>;
     fn update(&mut self, time: f32) -> HitBallState;
+    /// `samples` points along the flight of the last batted ball, up to where it lands.
+    fn path(&self, samples: usize) -> Vec<Vec3>;
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct Count {
+    pub balls: u32,
+    pub strikes: u32,
+}
+
+#[derive(Clone)]
+pub enum PitchCall {
+    Ball,
+    CalledStrike,
+    SwingingStrike,
+    Foul,
+    InPlay(HitResult),
+}
+
+impl PitchCall {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PitchCall::Ball => "BALL",
+            PitchCall::CalledStrike => "STRIKE",
+            PitchCall::SwingingStrike => "SWING AND MISS",
+            PitchCall::Foul => "FOUL",
+            PitchCall::InPlay(HitResult::Foul) => "FOUL",
+            PitchCall::InPlay(HitResult::SafeHit) => "HIT",
+            PitchCall::InPlay(HitResult::HomeRun) => "HOME RUN",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum AtBatResult {
+    Walk,
+    Strikeout,
+    InPlay(HitResult),
+}
+
+pub struct PitchOutcome {
+    pub call: PitchCall,
+    /// Count after the call. It is reset to 0-0 when the at-bat is over.
+    pub count: Count,
+    pub at_bat: Option<AtBatResult>,
+}
+
+pub trait Umpire {
+    type Config;
+    fn new(config: Self::Config) -> Self;
+    fn track_ball(&mut self, ball_position: Option<Vec3>);
+    fn call(&mut self, attempt: SwingAttempt, hit: Option<HitResult>) -> PitchOutcome;
+    fn count(&self) -> Count;
+}
+
+pub trait PitcherAi {
+    type Config;
+    fn new(config: Self::Config, seed: u32) -> Self;
+    fn set_difficulty(&mut self, difficulty: Difficulty);
+    fn choose(&mut self, count: Count) -> Pitch;
+    fn observe(&mut self, outcome: &PitchOutcome);
 }
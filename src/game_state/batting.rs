@@ -1,4 +1,6 @@
-use super::_interfaces::{Batting, BattingState, HitInfo};
+use super::_interfaces::{
+    Batting, BattingState, ContactQuality, Difficulty, HitInfo, SwingAttempt, SwingKind,
+};
 use crate::log;
 
 use webgl_matrix::{Vec3, Vector};
@@ -11,11 +13,20 @@ pub struct BatCoord {
 }
 
 pub trait BattingConfig {
-    fn normalized_time(&self, time: f32) -> f32;
+    fn set_difficulty(&mut self, difficulty: Difficulty);
+    fn normalized_time(&self, kind: SwingKind, time: f32) -> f32;
     fn constrain_batting_area(&self, batter_position: &Vec3) -> Vec3;
-    fn is_active_swing_time(&self, t: f32) -> bool;
-    fn bat_coord(&self, t: f32) -> BatCoord;
-    fn is_valid_meet(&self, meet: [f32; 2]) -> bool;
+    fn contact_plane(&self) -> (Vec3, Vec3);
+    /// Converts the contact cursor into (reach, height) offsets of the bat.
+    fn aim(&self, cursor: &Vec3) -> [f32; 2];
+    fn is_active_swing_time(&self, kind: SwingKind, t: f32) -> bool;
+    fn bat_coord(&self, kind: SwingKind, t: f32) -> BatCoord;
+    fn is_valid_meet(&self, kind: SwingKind, meet: [f32; 2]) -> bool;
+    fn contact_quality(&self, kind: SwingKind, t: f32, meet: [f32; 2]) -> ContactQuality;
+    fn exit_velocity_scale(&self, kind: SwingKind) -> f32;
+    fn cancel_limit(&self, kind: SwingKind) -> f32;
+    /// Swing degree of an aborted swing, `None` once the bat is back.
+    fn checked_degree(&self, kind: SwingKind, released_t: f32, time: f32) -> Option<f32>;
 }
 
 pub struct BattingImpl<C>
@@ -23,8 +34,12 @@ where
     C: BattingConfig,
 {
     is_swinging: bool,
+    swing_kind: SwingKind,
     swang_at: f32,
+    released_at: f32,
+    attempt: SwingAttempt,
     batter_position: Vec3,
+    aim: [f32; 2],
     last_ball_position_local: Vec3,
     config: C,
 }
@@ -37,46 +52,125 @@ where
     fn new(config: C) -> Self {
         Self {
             is_swinging: false,
+            swing_kind: SwingKind::Normal,
             swang_at: f32::MAX,
+            released_at: f32::MAX,
+            attempt: SwingAttempt::NoSwing,
             batter_position: [0., 0., 0.],
+            aim: [0., 0.],
             last_ball_position_local: [0., 0., 0.],
             config,
         }
     }
 
+    fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.config.set_difficulty(difficulty);
+    }
+
     fn set_batter_position(&mut self, position: Vec3) {
         if !self.is_swinging {
             self.batter_position = position;
         }
     }
 
-    fn swing(&mut self, swang_at: f32) {
+    fn contact_plane(&self) -> (Vec3, Vec3) {
+        self.config.contact_plane()
+    }
+
+    fn set_contact_cursor(&mut self, cursor: Vec3) {
+        if !self.is_swinging {
+            self.aim = self.config.aim(&cursor);
+        }
+    }
+
+    fn contact_cursor(&self) -> Vec3 {
+        let (center, _) = self.config.contact_plane();
+        center.add(&[self.aim[0], self.aim[1], 0.])
+    }
+
+    fn swing(&mut self, swang_at: f32, kind: SwingKind) {
         if !self.is_swinging {
             self.swang_at = swang_at;
+            self.swing_kind = kind;
+            self.released_at = f32::MAX;
+            self.attempt = SwingAttempt::Full;
             self.is_swinging = true;
         }
     }
 
+    fn release(&mut self, released_at: f32) {
+        if self.is_swinging && self.released_at == f32::MAX {
+            self.released_at = released_at;
+        }
+    }
+
+    fn take_attempt(&mut self) -> SwingAttempt {
+        let attempt = self.attempt;
+        self.attempt = SwingAttempt::NoSwing;
+        attempt
+    }
+
     fn update(&mut self, time: f32, ball_position: Option<Vec3>) -> BattingState {
-        let t = self.config.normalized_time(time - self.swang_at);
-        let batter_position = self.config.constrain_batting_area(&self.batter_position);
-        if t < 0. || 1. < t || ball_position.is_none() {
+        let kind = self.swing_kind;
+        let t = self.config.normalized_time(kind, time - self.swang_at);
+        let batter_position = self
+            .config
+            .constrain_batting_area(&self.batter_position.add(&[self.aim[0], 0., 0.]));
+        let idle = BattingState::Idle {
+            batter: batter_position,
+        };
+        if t < 0. {
+            self.is_swinging = false;
+            return idle;
+        }
+
+        let released_t = self
+            .config
+            .normalized_time(kind, self.released_at - self.swang_at);
+        let t = if released_t <= t && released_t < self.config.cancel_limit(kind) {
+            let checked = self
+                .config
+                .checked_degree(kind, released_t, time - self.released_at);
+            match checked {
+                Some(swing_degree) => {
+                    // The umpire judges how far the bat went, not where it is pulled back to.
+                    self.attempt = match self.attempt {
+                        SwingAttempt::Full => SwingAttempt::Checked(swing_degree),
+                        SwingAttempt::Checked(furthest) => {
+                            SwingAttempt::Checked(furthest.max(swing_degree))
+                        }
+                        SwingAttempt::NoSwing => SwingAttempt::NoSwing,
+                    };
+                    swing_degree
+                }
+                None => {
+                    self.is_swinging = false;
+                    return idle;
+                }
+            }
+        } else {
+            t
+        };
+        if 1. < t {
             self.is_swinging = false;
-            return BattingState::Idle {
-                batter: batter_position,
-            };
+            return idle;
         }
 
         let swinging = BattingState::Swinging {
             batter: batter_position,
+            swing_kind: kind,
             swing_degree: t,
         };
-        if !self.config.is_active_swing_time(t) {
+        if !self.config.is_active_swing_time(kind, t) {
             return swinging;
         }
 
-        let ball_position = ball_position.unwrap();
-        let bat_coord = self.config.bat_coord(t);
+        let ball_position = match ball_position {
+            Some(ball_position) => ball_position,
+            None => return swinging,
+        };
+        let mut bat_coord = self.config.bat_coord(kind, t);
+        bat_coord.origin[1] += self.aim[1];
         let point = ball_position.sub(&batter_position).sub(&bat_coord.origin);
         let x = point.dot(&bat_coord.x_axis);
         let y = point.dot(&bat_coord.y_axis);
@@ -85,7 +179,7 @@ where
         if z > 0. {
             self.last_ball_position_local = [x, y, z];
             return swinging;
-        } else if !self.config.is_valid_meet([x, y]) {
+        } else if !self.config.is_valid_meet(kind, [x, y]) {
             return swinging;
         }
         // TODO: stop following frame update
@@ -93,13 +187,25 @@ where
         let meet = self.last_ball_position_local.sub(&[x, y, z]);
         let z = -z / meet[2];
         let meet = [z * meet[0] + x, z * meet[1] + y];
+        let position = batter_position
+            .add(&bat_coord.origin)
+            .add(&bat_coord.x_axis.scale(meet[0]))
+            .add(&bat_coord.y_axis.scale(meet[1]));
 
-        BattingState::Hit(HitInfo {
-            x_axis: bat_coord.x_axis,
-            y_axis: bat_coord.y_axis,
-            z_axis: bat_coord.z_axis,
-            origin: bat_coord.origin,
-            meet_position: meet,
-        })
+        BattingState::Hit {
+            batter: batter_position,
+            swing_kind: kind,
+            swing_degree: t,
+            info: HitInfo {
+                x_axis: bat_coord.x_axis,
+                y_axis: bat_coord.y_axis,
+                z_axis: bat_coord.z_axis,
+                origin: bat_coord.origin,
+                meet_position: meet,
+                position,
+                quality: self.config.contact_quality(kind, t, meet),
+                exit_velocity_scale: self.config.exit_velocity_scale(kind),
+            },
+        }
     }
 }
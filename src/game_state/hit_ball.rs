@@ -1,11 +1,14 @@
-use super::_interfaces::{HitBall, HitBallState, HitInfo, HitResult};
+use super::_interfaces::{HitBall, HitBallState, HitInfo, HitResult, Trajectory};
+use crate::impls::vector::Normalize;
 
+use std::f32::consts::PI;
 use webgl_matrix::{Vec3, Vector};
 
 pub struct Config {
     duration: f32,
     ground_height: f32,
     gravity: f32,
+    exit_speed: f32,
     sector: Sector,
 }
 
@@ -16,19 +19,95 @@ pub struct Sector {
     radius: f32,
 }
 
+#[derive(Clone)]
 pub struct Parabola {
-    start: Vec3,
-    initial_velocity: Vec3,
+    pub start: Vec3,
+    pub initial_velocity: Vec3,
 }
 
 pub struct HitBallImpl {
     config: Config,
     hit_at: f32,
     parabola: Option<Parabola>,
+    trajectory: Option<Trajectory>,
     curr_result: Option<(f32, HitResult)>,
     last_ball_position: Vec3,
 }
 
+impl Config {
+    fn ground_y(&self) -> f32 {
+        self.sector.position[1] + self.ground_height
+    }
+}
+
+impl Sector {
+    /// Judges a point on the ground. Returns the horizontal distance from the home plate as well.
+    fn judge(&self, landing: &Vec3) -> (f32, HitResult) {
+        let p = landing.sub(&self.position);
+        let horizontal = [p[0], 0., p[2]];
+        let distance = horizontal.mag();
+        if distance < f32::EPSILON || self.direction.dot(&horizontal) / distance < self.angle_cos {
+            return (distance, HitResult::Foul);
+        }
+        let result = if distance < self.radius {
+            HitResult::SafeHit
+        } else {
+            HitResult::HomeRun
+        };
+        (distance, result)
+    }
+}
+
+impl Parabola {
+    /// `t` is normalized with `Config::duration`.
+    pub fn position(&self, gravity: f32, t: f32) -> Vec3 {
+        self.start
+            .add(&self.initial_velocity.scale(t))
+            .add(&[0., gravity * t * t * 0.5, 0.])
+    }
+}
+
+/// Predicts the whole flight of a batted ball from its launch state without stepping `HitBall::update`.
+/// Returns `None` if the ball never comes down onto the ground.
+pub fn predict_trajectory(config: &Config, launch: &Parabola) -> Option<Trajectory> {
+    let gravity = config.gravity;
+    if gravity >= 0. {
+        return None;
+    }
+    let v_y = launch.initial_velocity[1];
+    let height = launch.start[1] - config.ground_y();
+    let discriminant = v_y * v_y - 2. * gravity * height;
+    if discriminant < 0. {
+        return None;
+    }
+    let land_t = (-v_y - discriminant.sqrt()) / gravity;
+    if land_t <= 0. {
+        return None;
+    }
+    let apex_t = (-v_y / gravity).clamp(0., land_t);
+
+    let mut landing = launch.position(gravity, land_t);
+    landing[1] = config.ground_y();
+    let (distance, result) = config.sector.judge(&landing);
+
+    let exit_velocity = launch.initial_velocity.mag();
+    let launch_angle = if exit_velocity > 0. {
+        (v_y / exit_velocity).asin()
+    } else {
+        0.
+    };
+    Some(Trajectory {
+        exit_velocity,
+        launch_angle,
+        apex: launch.position(gravity, apex_t),
+        apex_time: apex_t * config.duration,
+        hang_time: land_t * config.duration,
+        landing,
+        distance,
+        result,
+    })
+}
+
 impl HitBall for HitBallImpl {
     type Config = Config;
     fn new(config: Self::Config) -> Self {
@@ -36,72 +115,60 @@ impl HitBall for HitBallImpl {
             config,
             hit_at: f32::MAX,
             parabola: None,
+            trajectory: None,
             curr_result: None,
             last_ball_position: [0., 0., 0.],
         }
     }
 
     fn hit(&mut self, timestamp: f32, info: HitInfo) {
+        let parabola = Parabola {
+            start: info.position,
+            initial_velocity: info.z_axis.normalize().scale(
+                self.config.exit_speed * info.exit_velocity_scale * info.quality.grade.power(),
+            ),
+        };
         self.hit_at = timestamp;
-        self.parabola = Some(Parabola {
-            start: [0., 0., 0.],
-            initial_velocity: [0., 0., 0.],
-        });
+        self.curr_result = None;
+        self.trajectory = predict_trajectory(&self.config, &parabola);
+        self.parabola = Some(parabola);
+    }
+
+    fn trajectory(&self) -> Option<&Trajectory> {
+        self.trajectory.as_ref()
     }
 
     fn update(&mut self, time: f32) -> HitBallState {
-        let t = (time - self.hit_at) / self.config.duration;
-        if t < 0. || 1. < t {
-            self.curr_result = None;
+        let elapsed = time - self.hit_at;
+        // A ball still in the air after `Config::duration` is followed until it is judged, as
+        // `predict_trajectory` expects it to be.
+        let is_over = match &self.curr_result {
+            Some((judged_at, _)) => elapsed > self.config.duration.max(judged_at - self.hit_at),
+            None => elapsed > self.config.duration && self.trajectory.is_none(),
+        };
+        if elapsed < 0. || is_over {
             return HitBallState::Idle {};
         }
+        let t = elapsed / self.config.duration;
         match &self.parabola {
             None => HitBallState::Idle {},
             Some(parabola) => {
-                let position = parabola.initial_velocity.scale(t).add(&[
-                    0.,
-                    self.config.gravity * t * t * 0.5,
-                    0.,
-                ]);
-
                 if let Some((judged_at, result)) = self.curr_result.as_ref() {
                     return HitBallState::Result {
-                        position,
+                        position: self.last_ball_position,
                         result: result.clone(),
                         judged_at: *judged_at,
                     };
                 }
 
                 // TODO: consider air resistance
-                let p = position.sub(&self.config.sector.position);
-
-                let p_u = p.scale(1. / p.mag());
-                let angle = self.config.sector.direction.dot(&p_u);
-
-                if self.config.sector.angle_cos < angle {
-                    self.last_ball_position = position;
-                    self.curr_result = Some((time, HitResult::Foul));
-                    return HitBallState::Result {
-                        result: HitResult::Foul,
-                        position,
-                        judged_at: time,
-                    };
-                }
-
-                if p[1] > self.config.ground_height {
+                let position = parabola.position(self.config.gravity, t);
+                if position[1] > self.config.ground_y() {
                     return HitBallState::Frying { position };
                 }
 
-                // TODO: do some calcuration to align ball position with the ground
-
-                let frying_distance = [p[0], 0., p[2]].mag();
-                self.last_ball_position = position;
-
-                let result = if frying_distance < self.config.sector.radius {
-                    HitResult::SafeHit
-                } else {
-                    HitResult::HomeRun
-                };
+                let position = [position[0], self.config.ground_y(), position[2]];
+                let (_, result) = self.config.sector.judge(&position);
 
                 self.last_ball_position = position;
                 self.curr_result = Some((time, result.clone()));
@@ -113,4 +180,142 @@ impl HitBall for HitBallImpl {
             }
         }
     }
+
+    fn path(&self, samples: usize) -> Vec<Vec3> {
+        let parabola = match &self.parabola {
+            Some(parabola) => parabola,
+            None => return vec![],
+        };
+        let end = self
+            .trajectory
+            .as_ref()
+            .map_or(1., |trajectory| trajectory.hang_time / self.config.duration);
+        let last = samples.saturating_sub(1).max(1) as f32;
+        (0..samples)
+            .map(|i| parabola.position(self.config.gravity, end * i as f32 / last))
+            .collect()
+    }
+}
+
+// Concrete Instances
+
+impl Config {
+    pub fn default() -> Self {
+        Self {
+            duration: 6000.,
+            ground_height: 0.,
+            gravity: -40.,
+            exit_speed: 20.,
+            sector: Sector {
+                position: [0., -1., 0.8],
+                direction: [0., 0., -1.],
+                angle_cos: (PI * 0.25).cos(),
+                radius: 6.,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_state::{ContactGrade, ContactQuality};
+
+    /// Frame time the ball is stepped with.
+    const STEP: f32 = 1000. / 60.;
+
+    fn hit(position: Vec3, direction: Vec3) -> HitInfo {
+        HitInfo {
+            x_axis: [1., 0., 0.],
+            y_axis: [0., 1., 0.],
+            z_axis: direction,
+            origin: position,
+            meet_position: [0., 0.],
+            position,
+            quality: ContactQuality {
+                timing: 0.,
+                grade: ContactGrade::Perfect,
+            },
+            exit_velocity_scale: 1.,
+        }
+    }
+
+    struct Flight {
+        /// Time and position of the highest frame.
+        highest: (f32, Vec3),
+        judged_at: f32,
+        position: Vec3,
+        result: HitResult,
+    }
+
+    /// Steps `update` frame by frame until the ball is judged.
+    fn fly(hit_ball: &mut HitBallImpl) -> Flight {
+        let mut time = 0.;
+        let mut highest = (0., [0., f32::MIN, 0.]);
+        loop {
+            match hit_ball.update(time) {
+                HitBallState::Result {
+                    position,
+                    result,
+                    judged_at,
+                } => {
+                    return Flight {
+                        highest,
+                        judged_at,
+                        position,
+                        result,
+                    }
+                }
+                HitBallState::Idle {} => panic!("the ball was never judged"),
+                HitBallState::Frying { position } => {
+                    if position[1] > highest.1[1] {
+                        highest = (time, position);
+                    }
+                    time += STEP;
+                }
+            }
+        }
+    }
+
+    fn assert_matches_prediction(position: Vec3, direction: Vec3) {
+        let mut hit_ball = HitBallImpl::new(Config::default());
+        hit_ball.hit(0., hit(position, direction));
+        let trajectory = match hit_ball.trajectory() {
+            Some(trajectory) => trajectory.clone(),
+            None => panic!("no trajectory was predicted"),
+        };
+
+        let flight = fly(&mut hit_ball);
+        // the highest frame is the one nearest to the apex
+        let (highest_at, highest) = flight.highest;
+        assert!((highest_at - trajectory.apex_time).abs() <= STEP);
+        assert!(highest.sub(&trajectory.apex).mag() < 0.1);
+        assert!(highest[1] <= trajectory.apex[1]);
+        // the ball is judged on the first frame it is under the ground
+        let hang_time = trajectory.hang_time;
+        assert!(flight.judged_at >= hang_time && flight.judged_at - hang_time <= STEP);
+        assert!(flight.position.sub(&trajectory.landing).mag() < 0.2);
+        assert!(flight.result == trajectory.result);
+    }
+
+    #[test]
+    fn line_drive_lands_where_predicted() {
+        assert_matches_prediction([0., -0.8, 0.8], [0.1, 0.4, -1.].normalize());
+    }
+
+    #[test]
+    fn fly_ball_lands_where_predicted() {
+        assert_matches_prediction([0., -0.8, 0.8], [0., 1., -1.].normalize());
+    }
+
+    #[test]
+    fn ball_in_the_air_after_the_duration_is_still_judged() {
+        let position = [0., 5., 0.8];
+        let direction = [0., 1., -0.3].normalize();
+        let mut hit_ball = HitBallImpl::new(Config::default());
+        hit_ball.hit(0., hit(position, direction));
+        let hang_time = hit_ball.trajectory().map(|trajectory| trajectory.hang_time);
+        assert!(hang_time > Some(hit_ball.config.duration));
+        assert_matches_prediction(position, direction);
+    }
 }
@@ -1,7 +1,9 @@
 mod _interfaces;
 pub mod batting;
-mod hit_ball;
+pub mod hit_ball;
+pub mod pitcher_ai;
 pub mod pitching;
+pub mod umpire;
 
 use std::cell::RefCell;
 use std::cell::{Ref, RefMut};
@@ -25,28 +27,58 @@ macro_rules! GameState {
             fn $borrow_mut(&self) -> RefMut<'_, Self::$state_trait_name>;
         }
     };
+    // states that are only ever driven, never just read
+    ($trait_name:ident, $state_trait_name:ident, $borrow_mut:ident) => {
+        pub trait $trait_name
+        where
+            Self::$state_trait_name: _interfaces::$state_trait_name,
+        {
+            type $state_trait_name;
+            fn $borrow_mut(&self) -> RefMut<'_, Self::$state_trait_name>;
+        }
+    };
 }
 
 GameState!(GameStateBatting, Batting, batting, batting_mut);
 GameState!(GameStatePitching, Pitching, pitching, pitching_mut);
-GameState!(GameStateHitBall, HitBall, hit_ball, hit_ball_mut);
+GameState!(GameStateHitBall, HitBall, hit_ball_mut);
+GameState!(GameStateUmpire, Umpire, umpire_mut);
+GameState!(GameStatePitcherAi, PitcherAi, pitcher_ai_mut);
 
-pub struct BattingSceneGameState<B, P> {
+pub struct BattingSceneGameState<B, P, H, U, A> {
     batting: Rc<RefCell<B>>,
     pitching: Rc<RefCell<P>>,
+    hit_ball: Rc<RefCell<H>>,
+    umpire: Rc<RefCell<U>>,
+    pitcher_ai: Rc<RefCell<A>>,
 }
 
-impl<B, P> BattingSceneGameState<B, P>
+impl<B, P, H, U, A> BattingSceneGameState<B, P, H, U, A>
 where
     B: Batting,
     P: Pitching,
+    H: HitBall,
+    U: Umpire,
+    A: PitcherAi,
 {
-    pub fn new(batting: Rc<RefCell<B>>, pitching: Rc<RefCell<P>>) -> Self {
-        Self { batting, pitching }
+    pub fn new(
+        batting: Rc<RefCell<B>>,
+        pitching: Rc<RefCell<P>>,
+        hit_ball: Rc<RefCell<H>>,
+        umpire: Rc<RefCell<U>>,
+        pitcher_ai: Rc<RefCell<A>>,
+    ) -> Self {
+        Self {
+            batting,
+            pitching,
+            hit_ball,
+            umpire,
+            pitcher_ai,
+        }
     }
 }
 
-impl<B, P> GameStateBatting for BattingSceneGameState<B, P>
+impl<B, P, H, U, A> GameStateBatting for BattingSceneGameState<B, P, H, U, A>
 where
     B: Batting,
 {
@@ -59,7 +91,7 @@ where
     }
 }
 
-impl<B, P> GameStatePitching for BattingSceneGameState<B, P>
+impl<B, P, H, U, A> GameStatePitching for BattingSceneGameState<B, P, H, U, A>
 where
     P: Pitching,
 {
@@ -71,3 +103,33 @@ where
         self.pitching.borrow_mut()
     }
 }
+
+impl<B, P, H, U, A> GameStateHitBall for BattingSceneGameState<B, P, H, U, A>
+where
+    H: HitBall,
+{
+    type HitBall = H;
+    fn hit_ball_mut(&self) -> RefMut<'_, Self::HitBall> {
+        self.hit_ball.borrow_mut()
+    }
+}
+
+impl<B, P, H, U, A> GameStateUmpire for BattingSceneGameState<B, P, H, U, A>
+where
+    U: Umpire,
+{
+    type Umpire = U;
+    fn umpire_mut(&self) -> RefMut<'_, Self::Umpire> {
+        self.umpire.borrow_mut()
+    }
+}
+
+impl<B, P, H, U, A> GameStatePitcherAi for BattingSceneGameState<B, P, H, U, A>
+where
+    A: PitcherAi,
+{
+    type PitcherAi = A;
+    fn pitcher_ai_mut(&self) -> RefMut<'_, Self::PitcherAi> {
+        self.pitcher_ai.borrow_mut()
+    }
+}
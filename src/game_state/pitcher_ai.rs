@@ -0,0 +1,200 @@
+use super::_interfaces::{Count, Difficulty, Pitch, PitchCall, PitchKind, PitchOutcome, PitcherAi};
+use crate::random::Random;
+
+use std::collections::VecDeque;
+
+pub trait PitcherAiConfig {
+    fn set_difficulty(&mut self, difficulty: Difficulty);
+    fn pitch_mix(&self) -> &[(PitchKind, f32)];
+    /// Probability of aiming inside the strike zone with the given count.
+    fn zone_rate(&self, count: &Count) -> f32;
+    fn zone_half_size(&self) -> [f32; 2];
+    /// Maximum distance the ball misses the target by.
+    fn control_error(&self) -> f32;
+    /// Number of recent pitches the pitcher remembers the batter's reaction to.
+    fn memory(&self) -> usize;
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SwingResult {
+    Take,
+    Whiff,
+    Foul,
+    Contact,
+}
+
+pub struct PitcherAiImpl<C>
+where
+    C: PitcherAiConfig,
+{
+    config: C,
+    random: Random,
+    last_pitch: Option<Pitch>,
+    history: VecDeque<(PitchKind, SwingResult)>,
+}
+
+impl<C> PitcherAiImpl<C>
+where
+    C: PitcherAiConfig,
+{
+    fn choose_kind(&mut self, count: &Count) -> PitchKind {
+        let weights = self
+            .config
+            .pitch_mix()
+            .iter()
+            .map(|(kind, weight)| {
+                let mut weight = *weight;
+                for (prev_kind, result) in &self.history {
+                    if prev_kind == kind {
+                        match result {
+                            SwingResult::Whiff => weight *= 1.5,
+                            SwingResult::Contact => weight *= 0.6,
+                            _ => {}
+                        }
+                    }
+                }
+                // Falling behind, go for the pitch that is the easiest to control.
+                if count.balls > count.strikes + 1 && *kind == PitchKind::Fastball {
+                    weight *= 2.;
+                }
+                (*kind, weight)
+            })
+            .collect::<Vec<_>>();
+
+        let total = weights.iter().map(|(_, weight)| weight).sum::<f32>();
+        let mut r = self.random.next_f32() * total;
+        for (kind, weight) in &weights {
+            if r < *weight {
+                return *kind;
+            }
+            r -= weight;
+        }
+        weights
+            .last()
+            .map_or(PitchKind::Fastball, |(kind, _)| *kind)
+    }
+
+    fn choose_target(&mut self, count: &Count) -> [f32; 2] {
+        let [w, h] = self.config.zone_half_size();
+        let target = if self.random.next_f32() < self.config.zone_rate(count) {
+            [
+                self.random.range(-0.8, 0.8) * w,
+                self.random.range(-0.8, 0.8) * h,
+            ]
+        } else {
+            // just off one of the edges
+            let along = self.random.range(-1., 1.);
+            let off = self.random.range(1.1, 1.5);
+            match self.random.next_u32() % 4 {
+                0 => [along * w, off * h],
+                1 => [along * w, -off * h],
+                2 => [off * w, along * h],
+                _ => [-off * w, along * h],
+            }
+        };
+        let error = self.config.control_error();
+        [
+            target[0] + self.random.range(-error, error),
+            target[1] + self.random.range(-error, error),
+        ]
+    }
+}
+
+impl<C> PitcherAi for PitcherAiImpl<C>
+where
+    C: PitcherAiConfig,
+{
+    type Config = C;
+
+    fn new(config: C, seed: u32) -> Self {
+        Self {
+            config,
+            random: Random::new(seed),
+            last_pitch: None,
+            history: VecDeque::new(),
+        }
+    }
+
+    fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.config.set_difficulty(difficulty);
+    }
+
+    fn choose(&mut self, count: Count) -> Pitch {
+        let kind = self.choose_kind(&count);
+        let target = self.choose_target(&count);
+        let pitch = Pitch {
+            kind,
+            target,
+            speed: 1.,
+        };
+        self.last_pitch = Some(pitch.clone());
+        pitch
+    }
+
+    fn observe(&mut self, outcome: &PitchOutcome) {
+        if let Some(pitch) = self.last_pitch.take() {
+            let result = match outcome.call {
+                PitchCall::Ball | PitchCall::CalledStrike => SwingResult::Take,
+                PitchCall::SwingingStrike => SwingResult::Whiff,
+                PitchCall::Foul => SwingResult::Foul,
+                PitchCall::InPlay(_) => SwingResult::Contact,
+            };
+            self.history.push_back((pitch.kind, result));
+            while self.history.len() > self.config.memory() {
+                self.history.pop_front();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::pitcher_ai::PitcherAiConfigImpl;
+    use crate::game_state::HitResult;
+
+    /// Pitches chosen by an AI seeded with `seed` while the batter reacts the same way every time.
+    fn game_log(seed: u32) -> Vec<(PitchKind, [f32; 2])> {
+        let mut ai = PitcherAiImpl::new(PitcherAiConfigImpl::default(), seed);
+        let mut count = Count::default();
+        (0..40)
+            .map(|i| {
+                let pitch = ai.choose(count);
+                let call = match i % 5 {
+                    0 => PitchCall::Ball,
+                    1 => PitchCall::SwingingStrike,
+                    2 => PitchCall::Foul,
+                    3 => PitchCall::CalledStrike,
+                    _ => PitchCall::InPlay(HitResult::SafeHit),
+                };
+                count = match call {
+                    PitchCall::Ball => Count {
+                        balls: (count.balls + 1) % 4,
+                        ..count
+                    },
+                    PitchCall::InPlay(_) => Count::default(),
+                    _ => Count {
+                        strikes: (count.strikes + 1).min(2),
+                        ..count
+                    },
+                };
+                ai.observe(&PitchOutcome {
+                    call,
+                    count,
+                    at_bat: None,
+                });
+                (pitch.kind, pitch.target)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_replays_the_same_pitches() {
+        assert!(game_log(42) == game_log(42));
+    }
+
+    #[test]
+    fn other_seed_throws_other_pitches() {
+        assert!(game_log(42) != game_log(43));
+    }
+}
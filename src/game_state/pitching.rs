@@ -1,14 +1,19 @@
-use super::_interfaces::{PitcherState, Pitching, PitchingState};
+use super::_interfaces::{Difficulty, Pitch, PitcherState, Pitching, PitchingState};
 
 use webgl_matrix::Vec3;
 
 pub trait PitchingConfig {
+    type Curve;
+    fn set_difficulty(&mut self, difficulty: Difficulty);
     fn next_idle_break(&self, offset: f32, timestamp: f32) -> f32;
     fn pre_idle_parameter(&self, offset: f32, time: f32) -> f32;
     fn post_idle_parameter(&self, time: f32) -> f32;
     fn pitching_parameter(&self, time: f32) -> f32;
-    fn ball_parameter(&self, time: f32) -> f32;
-    fn ball_position(&self, t: f32) -> Option<Vec3>;
+    fn pitcher_position(&self) -> Vec3;
+    fn ball_parameter(&self, pitch: &Pitch, time: f32) -> f32;
+    fn speed(&self, pitch: &Pitch) -> f32;
+    fn curve(&self, pitch: &Pitch) -> Self::Curve;
+    fn ball_position(&self, curve: &Self::Curve, t: f32) -> Option<Vec3>;
 }
 
 pub struct PitchingImpl<C>
@@ -19,6 +24,7 @@ where
     idle_offset: f32,
     pitched_at: f32,
     is_pitching: bool,
+    pitch: Option<(Pitch, C::Curve)>,
 }
 
 impl<C> Pitching for PitchingImpl<C>
@@ -33,22 +39,38 @@ where
             idle_offset: 0.,
             pitched_at: f32::MAX,
             is_pitching: false,
+            pitch: None,
         }
     }
 
+    fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.config.set_difficulty(difficulty);
+    }
+
     fn reset_idle(&mut self, timestamp: f32) {
         self.idle_offset = timestamp;
     }
 
-    fn pitch(&mut self, timestamp: f32) {
+    fn pitch(&mut self, timestamp: f32, pitch: Pitch) {
         if !self.is_pitching {
             self.pitched_at = self.config.next_idle_break(self.idle_offset, timestamp);
             self.is_pitching = true;
+            let curve = self.config.curve(&pitch);
+            self.pitch = Some((pitch, curve));
         }
     }
 
+    fn is_pitching(&self) -> bool {
+        self.is_pitching
+    }
+
+    fn speed(&self, pitch: &Pitch) -> f32 {
+        self.config.speed(pitch)
+    }
+
     fn end(&mut self) {
         self.pitched_at = f32::MAX;
+        self.is_pitching = false;
     }
 
     fn update(&mut self, time: f32) -> PitchingState {
@@ -56,18 +78,20 @@ where
         if time < 0. {
             return PitchingState {
                 pitcher: PitcherState::Idle(self.config.pre_idle_parameter(self.idle_offset, time)),
+                pitcher_position: self.config.pitcher_position(),
                 ball_position: None,
             };
         }
 
-        let ball_parameter = self.config.ball_parameter(time);
         let pitching_parameter = self.config.pitching_parameter(time);
-
-        let ball_position = if ball_parameter < 0. || 1. < ball_parameter {
-            None
-        } else {
-            self.config.ball_position(ball_parameter)
-        };
+        let ball_position = self.pitch.as_ref().and_then(|(pitch, curve)| {
+            let ball_parameter = self.config.ball_parameter(pitch, time);
+            if ball_parameter < 0. || 1. < ball_parameter {
+                None
+            } else {
+                self.config.ball_position(curve, ball_parameter)
+            }
+        });
 
         let pitcher = if pitching_parameter < 1. {
             PitcherState::Pitching(pitching_parameter)
@@ -84,7 +108,19 @@ where
 
         PitchingState {
             pitcher,
+            pitcher_position: self.config.pitcher_position(),
             ball_position,
         }
     }
+
+    fn path(&self, samples: usize) -> Vec<Vec3> {
+        let (_, curve) = match &self.pitch {
+            Some(pitch) => pitch,
+            None => return vec![],
+        };
+        let last = samples.saturating_sub(1).max(1) as f32;
+        (0..samples)
+            .filter_map(|i| self.config.ball_position(curve, i as f32 / last))
+            .collect()
+    }
 }
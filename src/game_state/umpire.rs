@@ -0,0 +1,157 @@
+use super::_interfaces::{
+    AtBatResult, Count, HitResult, PitchCall, PitchOutcome, SwingAttempt, Umpire,
+};
+
+use webgl_matrix::{Vec3, Vector};
+
+pub trait UmpireConfig {
+    fn plate_depth(&self) -> f32;
+    fn is_in_zone(&self, position: &Vec3) -> bool;
+    fn is_swing(&self, attempt: SwingAttempt) -> bool;
+    fn balls_for_walk(&self) -> u32;
+    fn strikes_for_out(&self) -> u32;
+}
+
+pub struct UmpireImpl<C>
+where
+    C: UmpireConfig,
+{
+    config: C,
+    count: Count,
+    last_ball_position: Option<Vec3>,
+    is_in_zone: bool,
+}
+
+impl<C> Umpire for UmpireImpl<C>
+where
+    C: UmpireConfig,
+{
+    type Config = C;
+
+    fn new(config: C) -> Self {
+        Self {
+            config,
+            count: Count::default(),
+            last_ball_position: None,
+            is_in_zone: false,
+        }
+    }
+
+    fn track_ball(&mut self, ball_position: Option<Vec3>) {
+        if let (Some(last), Some(curr)) = (self.last_ball_position, ball_position) {
+            let depth = self.config.plate_depth();
+            let (d_last, d_curr) = (last[2] - depth, curr[2] - depth);
+            if d_last * d_curr <= 0. && d_last != d_curr {
+                let crossing = last.add(&curr.sub(&last).scale(d_last / (d_last - d_curr)));
+                self.is_in_zone = self.config.is_in_zone(&crossing);
+            }
+        }
+        self.last_ball_position = ball_position;
+    }
+
+    fn call(&mut self, attempt: SwingAttempt, hit: Option<HitResult>) -> PitchOutcome {
+        let call = match hit {
+            Some(HitResult::Foul) => PitchCall::Foul,
+            Some(result) => PitchCall::InPlay(result),
+            None if self.config.is_swing(attempt) => PitchCall::SwingingStrike,
+            None if self.is_in_zone => PitchCall::CalledStrike,
+            None => PitchCall::Ball,
+        };
+        self.last_ball_position = None;
+        self.is_in_zone = false;
+
+        let at_bat = match &call {
+            PitchCall::Ball => {
+                self.count.balls += 1;
+                if self.count.balls < self.config.balls_for_walk() {
+                    None
+                } else {
+                    Some(AtBatResult::Walk)
+                }
+            }
+            PitchCall::CalledStrike | PitchCall::SwingingStrike => {
+                self.count.strikes += 1;
+                if self.count.strikes < self.config.strikes_for_out() {
+                    None
+                } else {
+                    Some(AtBatResult::Strikeout)
+                }
+            }
+            PitchCall::Foul => {
+                // A foul ball never makes the last strike.
+                if self.count.strikes + 1 < self.config.strikes_for_out() {
+                    self.count.strikes += 1;
+                }
+                None
+            }
+            PitchCall::InPlay(result) => Some(AtBatResult::InPlay(result.clone())),
+        };
+        if at_bat.is_some() {
+            self.count = Count::default();
+        }
+
+        PitchOutcome {
+            call,
+            count: self.count,
+            at_bat,
+        }
+    }
+
+    fn count(&self) -> Count {
+        self.count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::umpire::UmpireConfigImpl;
+
+    /// Center of the strike zone of the default config.
+    const ZONE: [f32; 2] = [0., -0.5];
+
+    /// Calls a pitch that crosses the plate at `[x, y]` with the batter's `attempt`.
+    fn call_pitch(attempt: SwingAttempt, [x, y]: [f32; 2]) -> PitchCall {
+        let mut umpire = UmpireImpl::new(UmpireConfigImpl::default());
+        umpire.track_ball(Some([x, y, 0.6]));
+        umpire.track_ball(Some([x, y, 1.]));
+        umpire.track_ball(None);
+        umpire.call(attempt, None).call
+    }
+
+    #[test]
+    fn check_swing_under_the_limit_is_a_take() {
+        let attempt = SwingAttempt::Checked(0.2);
+        assert!(matches!(
+            call_pitch(attempt, [0.6, ZONE[1]]),
+            PitchCall::Ball
+        ));
+        assert!(matches!(call_pitch(attempt, ZONE), PitchCall::CalledStrike));
+    }
+
+    #[test]
+    fn check_swing_over_the_limit_is_a_strike() {
+        let attempt = SwingAttempt::Checked(0.5);
+        assert!(matches!(
+            call_pitch(attempt, [0.6, ZONE[1]]),
+            PitchCall::SwingingStrike
+        ));
+    }
+
+    #[test]
+    fn zone_is_judged_where_the_ball_crosses_the_plate() {
+        // neither frame is over the plate, but the ball crosses the middle of the zone
+        let mut umpire = UmpireImpl::new(UmpireConfigImpl::default());
+        umpire.track_ball(Some([-0.4, ZONE[1], 0.6]));
+        umpire.track_ball(Some([0.4, ZONE[1], 1.]));
+        let call = umpire.call(SwingAttempt::NoSwing, None).call;
+        assert!(matches!(call, PitchCall::CalledStrike));
+
+        // the first frame is in line with the zone, but the ball crosses outside it
+        let mut umpire = UmpireImpl::new(UmpireConfigImpl::default());
+        umpire.track_ball(Some([0., ZONE[1], 0.6]));
+        umpire.track_ball(Some([0.6, ZONE[1], 1.]));
+        let call = umpire.call(SwingAttempt::NoSwing, None).call;
+        assert!(matches!(call, PitchCall::Ball));
+    }
+}
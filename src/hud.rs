@@ -0,0 +1,160 @@
+use crate::camera::{Anchor, UiCameraController};
+use crate::entities::thrown_ball::ThrownBall;
+use crate::entities::{get_current_instances, Renderable};
+use crate::game_state::{Count, Pitch, PitchKind, PitchOutcome, Trajectory};
+use crate::modes::Scoreboard;
+use crate::shader::{Atlas, BitmapFont};
+use crate::shaders::text_shader::Glyph;
+use crate::shaders::ui_shader::Sprite;
+use crate::text::{layout_text, Align, TextStyle};
+
+/// Number of lights of balls, strikes and outs. The light that would end the at-bat or the half
+/// inning is never lit, as on a real scoreboard.
+const BALL_LIGHTS: u32 = 3;
+const STRIKE_LIGHTS: u32 = 2;
+const OUT_LIGHTS: u32 = 2;
+/// Pixels from the corners of the screen to the HUD.
+const MARGIN: f32 = 16.;
+const TEXT_SCALE: f32 = 0.75;
+/// Pixels between the rows of the HUD.
+const ROW_HEIGHT: f32 = 28.;
+/// Pixels between the centers of lights.
+const LIGHT_SPACING: f32 = 20.;
+const LIGHT_SCALE: f32 = 140.;
+const UNLIT_OPACITY: f32 = 0.25;
+
+/// Scoreboard and count drawn in the UI pass. It only knows what it has been told through the
+/// `on_*` events, which the scene sends as the game state changes.
+pub struct Hud {
+    light: ThrownBall,
+    /// `None` if the mode doesn't keep count.
+    count: Option<Count>,
+    scoreboard: Option<Scoreboard>,
+    /// Type and speed of the last pitch thrown.
+    last_pitch: Option<(PitchKind, f32)>,
+    /// Last ball put in play.
+    last_trajectory: Option<Trajectory>,
+}
+
+impl Hud {
+    pub fn new(atlas: &Atlas, keeps_count: bool) -> Result<Self, String> {
+        Ok(Self {
+            light: ThrownBall::new(atlas)?,
+            count: if keeps_count {
+                Some(Count::default())
+            } else {
+                None
+            },
+            scoreboard: None,
+            last_pitch: None,
+            last_trajectory: None,
+        })
+    }
+
+    /// Called when `pitch` is thrown at `speed` miles per hour.
+    pub fn on_pitch(&mut self, pitch: &Pitch, speed: f32) {
+        self.last_pitch = Some((pitch.kind, speed));
+    }
+
+    /// Called once for every pitch that has been called by the umpire, as
+    /// `GameMode::on_outcome`.
+    pub fn on_outcome(&mut self, outcome: &PitchOutcome, trajectory: Option<&Trajectory>) {
+        if let Some(count) = &mut self.count {
+            *count = outcome.count;
+        }
+        if let Some(trajectory) = trajectory {
+            self.last_trajectory = Some(trajectory.clone());
+        }
+    }
+
+    /// Called whenever the mode may have changed the inning, the outs or the score.
+    pub fn on_scoreboard(&mut self, scoreboard: Option<Scoreboard>) {
+        self.scoreboard = scoreboard;
+    }
+
+    pub fn draw(
+        &mut self,
+        ui_camera: &UiCameraController,
+        font: &BitmapFont,
+        sprites: &mut Vec<Sprite>,
+        glyphs: &mut Vec<Glyph>,
+    ) {
+        let text = |color: [f32; 4], align: Align| TextStyle::new(align, color, TEXT_SCALE);
+        let white = [1., 1., 1., 1.];
+        let mut row = 0.;
+
+        if let Some(scoreboard) = &self.scoreboard {
+            let inning = format!(
+                "{} {}  P1 {} - P2 {}",
+                if scoreboard.is_bottom { "BOT" } else { "TOP" },
+                scoreboard.inning,
+                scoreboard.runs[0],
+                scoreboard.runs[1],
+            );
+            glyphs.extend(layout_text(
+                font,
+                &inning,
+                ui_camera.anchor(Anchor::TopLeft, [MARGIN, MARGIN]),
+                &text(white, Align::Left),
+            ));
+            row += 1.;
+        }
+        let mut lights = vec![];
+        if let Some(count) = &self.count {
+            lights.push(("B", [0.4, 1., 0.5, 1.], count.balls, BALL_LIGHTS));
+            lights.push(("S", [1., 0.85, 0.2, 1.], count.strikes, STRIKE_LIGHTS));
+        }
+        if let Some(scoreboard) = &self.scoreboard {
+            lights.push(("O", [1., 0.35, 0.3, 1.], scoreboard.outs, OUT_LIGHTS));
+        }
+        for (label, color, lit, total) in lights {
+            let [x, y] = ui_camera.anchor(Anchor::TopLeft, [MARGIN, MARGIN + row * ROW_HEIGHT]);
+            glyphs.extend(layout_text(font, label, [x, y], &text(color, Align::Left)));
+            // lights are centered on the line of the label
+            let cy = y + font.line_height * TEXT_SCALE / 2.;
+            for i in 0..total {
+                let cx = x + LIGHT_SPACING * (i as f32 + 1.5);
+                let s = LIGHT_SCALE;
+                self.light.set_model([
+                    s, 0., 0., 0., //
+                    0., s, 0., 0., //
+                    0., 0., s, 0., //
+                    cx, cy, 0., 1., //
+                ]);
+                let opacity = if i < lit { 1. } else { UNLIT_OPACITY };
+                sprites.extend(
+                    get_current_instances(&self.light)
+                        .into_iter()
+                        .map(|instance| {
+                            let mut sprite = Sprite::from(instance);
+                            sprite.opacity *= opacity;
+                            sprite
+                        }),
+                );
+            }
+            row += 1.;
+        }
+
+        let mut lines = vec![];
+        if let Some((kind, speed)) = &self.last_pitch {
+            lines.push(format!("{} {:.0} MPH", kind.label(), speed));
+        }
+        if let Some(trajectory) = &self.last_trajectory {
+            lines.push(format!("DIST {:.1}", trajectory.distance));
+            // the ball lands on the ground, so the apex is measured from the landing point
+            lines.push(format!(
+                "APEX {:.1} AT {:.1}S",
+                trajectory.apex[1] - trajectory.landing[1],
+                trajectory.apex_time / 1000.
+            ));
+        }
+        if !lines.is_empty() {
+            glyphs.extend(layout_text(
+                font,
+                &lines.join("\n"),
+                ui_camera.anchor(Anchor::TopRight, [-MARGIN, MARGIN]),
+                &text(white, Align::Right),
+            ));
+        }
+    }
+}
@@ -1,61 +1,155 @@
 use crate::now;
+use crate::viewport::Viewport;
 
 use core::cell::RefCell;
 use num_traits::ToPrimitive;
 use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
-use web_sys::HtmlCanvasElement;
+use web_sys::{HtmlCanvasElement, Window};
 
 type Element = HtmlCanvasElement;
 
-pub struct ClickEvent {
+pub struct Modifiers {
+    pub shift: bool,
+    pub alt: bool,
+    pub ctrl: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Modifier {
+    Shift,
+    Alt,
+    Ctrl,
+}
+
+impl Modifier {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Modifier::Shift => "shift",
+            Modifier::Alt => "alt",
+            Modifier::Ctrl => "ctrl",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "shift" => Some(Modifier::Shift),
+            "alt" => Some(Modifier::Alt),
+            "ctrl" => Some(Modifier::Ctrl),
+            _ => None,
+        }
+    }
+}
+
+impl Modifiers {
+    pub fn is_held(&self, modifier: Modifier) -> bool {
+        match modifier {
+            Modifier::Shift => self.shift,
+            Modifier::Alt => self.alt,
+            Modifier::Ctrl => self.ctrl,
+        }
+    }
+}
+
+/// Modifier keys held on pressing to choose a swing other than the normal one.
+#[derive(Clone, Copy, PartialEq)]
+pub struct SwingBindings {
+    pub power: Modifier,
+    pub contact: Modifier,
+    pub bunt: Modifier,
+}
+
+impl SwingBindings {
+    pub fn default() -> Self {
+        Self {
+            power: Modifier::Shift,
+            contact: Modifier::Alt,
+            bunt: Modifier::Ctrl,
+        }
+    }
+}
+
+pub struct PressEvent {
     pub timestamp: f32,
+    pub modifiers: Modifiers,
     coord: (f32, f32),
 }
 
+pub struct ReleaseEvent {
+    pub timestamp: f32,
+}
+
 pub struct InputState {
-    pub clicked: Option<ClickEvent>,
+    pub pressed: Option<PressEvent>,
+    pub released: Option<ReleaseEvent>,
     pub curr_coord: (f32, f32),
     prev_coord: (f32, f32),
+    /// `KeyboardEvent.key` of the keys pressed since the last frame.
+    pub keys: Vec<String>,
 }
 
 impl InputState {
     pub fn resolve(&mut self) {
-        self.clicked = None;
+        self.pressed = None;
+        self.released = None;
         self.prev_coord = self.curr_coord;
+        self.keys.clear();
     }
 }
 
-pub fn set_input_handler(element: Rc<Element>) -> Rc<RefCell<InputState>> {
+pub fn set_input_handler(
+    window: Rc<Window>,
+    element: Rc<Element>,
+    viewport: Rc<RefCell<Viewport>>,
+) -> Rc<RefCell<InputState>> {
     let state = Rc::new(RefCell::new(InputState {
-        clicked: None,
+        pressed: None,
+        released: None,
         curr_coord: (0., 0.),
         prev_coord: (0., 0.),
+        keys: vec![],
     }));
 
-    let onclick = Closure::wrap(Box::new({
+    let onmousedown = Closure::wrap(Box::new({
         let state = state.clone();
-        let element = element.clone();
+        let viewport = viewport.clone();
         move |event: web_sys::MouseEvent| {
             let mut state = state.borrow_mut();
-            state.clicked = Some(ClickEvent {
+            state.pressed = Some(PressEvent {
                 timestamp: now(),
-                coord: get_mouse_coord(&event, &element),
+                modifiers: Modifiers {
+                    shift: event.shift_key(),
+                    alt: event.alt_key(),
+                    ctrl: event.ctrl_key() || event.meta_key(),
+                },
+                coord: get_mouse_coord(&event, &viewport.borrow()),
             });
             Ok(())
         }
     })
         as Box<dyn FnMut(web_sys::MouseEvent) -> Result<(), JsValue>>);
-    element.set_onclick(Some(onclick.as_ref().unchecked_ref()));
-    onclick.forget();
+    element.set_onmousedown(Some(onmousedown.as_ref().unchecked_ref()));
+    onmousedown.forget();
+
+    let onmouseup = Closure::wrap(Box::new({
+        let state = state.clone();
+        move |_: web_sys::MouseEvent| {
+            let mut state = state.borrow_mut();
+            state.released = Some(ReleaseEvent { timestamp: now() });
+            Ok(())
+        }
+    })
+        as Box<dyn FnMut(web_sys::MouseEvent) -> Result<(), JsValue>>);
+    // A press on the canvas may be released outside of it.
+    window.set_onmouseup(Some(onmouseup.as_ref().unchecked_ref()));
+    onmouseup.forget();
 
     let onmousemove = Closure::wrap(Box::new({
         let state = state.clone();
-        let element = element.clone();
         move |event: web_sys::MouseEvent| {
             let mut state = state.borrow_mut();
-            state.curr_coord = get_mouse_coord(&event, &element);
+            state.curr_coord = get_mouse_coord(&event, &viewport.borrow());
             Ok(())
         }
     })
@@ -63,15 +157,25 @@ pub fn set_input_handler(element: Rc<Element>) -> Rc<RefCell<InputState>> {
     element.set_onmousemove(Some(onmousemove.as_ref().unchecked_ref()));
     onmousemove.forget();
 
+    // The canvas doesn't receive key events without focus.
+    let onkeydown = Closure::wrap(Box::new({
+        let state = state.clone();
+        move |event: web_sys::KeyboardEvent| {
+            if !event.repeat() {
+                state.borrow_mut().keys.push(event.key());
+            }
+            Ok(())
+        }
+    })
+        as Box<dyn FnMut(web_sys::KeyboardEvent) -> Result<(), JsValue>>);
+    window.set_onkeydown(Some(onkeydown.as_ref().unchecked_ref()));
+    onkeydown.forget();
+
     state
 }
 
-fn get_mouse_coord(event: &web_sys::MouseEvent, element: &Element) -> (f32, f32) {
+fn get_mouse_coord(event: &web_sys::MouseEvent, viewport: &Viewport) -> (f32, f32) {
     let offset_x = event.offset_x().to_f32().unwrap();
     let offset_y = event.offset_y().to_f32().unwrap();
-    let width = element.width().to_f32().unwrap();
-    let height = element.height().to_f32().unwrap();
-    let x = (2. * offset_x / width) - 1.;
-    let y = 1. - (2. * offset_y / height);
-    (x, y)
+    viewport.to_ndc(offset_x, offset_y)
 }
@@ -3,28 +3,48 @@ mod camera;
 mod configs;
 mod entities;
 mod game_state;
+mod hud;
 mod impls;
 mod input;
 mod log;
+mod modes;
+mod random;
+mod save;
 mod scenes;
 mod scheduler;
 mod shader;
 mod shaders;
+mod stats;
+mod text;
 mod utils;
+mod viewport;
 
 use crate::configs::batting::BattingConfigImpl;
+use crate::configs::pitcher_ai::PitcherAiConfigImpl;
 use crate::configs::pitching::PitchingConfigImpl;
+use crate::configs::umpire::UmpireConfigImpl;
 use crate::game_state::batting::BattingImpl;
+use crate::game_state::hit_ball::{self, HitBallImpl};
+use crate::game_state::pitcher_ai::PitcherAiImpl;
 use crate::game_state::pitching::PitchingImpl;
-use crate::game_state::{Batting, BattingSceneGameState, Pitching};
+use crate::game_state::umpire::UmpireImpl;
+use crate::game_state::{Batting, BattingSceneGameState, HitBall, PitcherAi, Pitching, Umpire};
 use crate::input::set_input_handler;
+use crate::modes::{Career, Derby, GameMode, Practice, Versus};
+use crate::save::{LocalStorage, MemoryStorage, SaveManager, Storage};
+use crate::scenes::{HandoffScene, HandoffSceneContext};
 use crate::scenes::{SampleScene, SampleSceneContext, TestScene, TestSceneContext};
 use crate::scenes::{SceneManager, SceneType};
 use crate::scheduler::start_loop;
-use crate::shader::{ConvertArrayView, ShaderController, SharedContext};
+use crate::shader::{BitmapFont, ConvertArrayView, ShaderController, SharedContext};
 use crate::shaders::background_shader::BackgroundShader;
 use crate::shaders::entity_shader::EntityShader;
+use crate::shaders::line_shader::LineShader;
+use crate::shaders::shadow_shader::ShadowShader;
 use crate::shaders::test::TestShader;
+use crate::shaders::text_shader::{TextShader, FONT_PAGE};
+use crate::shaders::ui_shader::UiShader;
+use crate::viewport::watch_viewport;
 
 use num_traits::cast::ToPrimitive;
 use std::cell::RefCell;
@@ -57,6 +77,48 @@ pub fn now() -> f32 {
     }
 }
 
+/// Selects the game mode from the URL hash, e.g. `#derby`, `#practice/slider/0.8/0.1/-0.2` or
+/// `#versus/pitcher`.
+fn game_mode(window: &web_sys::Window) -> Box<dyn GameMode> {
+    let hash = window.location().hash().unwrap_or_default();
+    let path = hash.trim_start_matches('#').split('?').next().unwrap_or("");
+    let args = path.split('/').collect::<Vec<_>>();
+    match args[0] {
+        "derby" => Box::new(Derby::default()),
+        "practice" => {
+            let mut practice = Practice::default();
+            practice.machine_mut().configure(&args[1..]);
+            Box::new(practice)
+        }
+        "versus" => Box::new(Versus::default(args.get(1) == Some(&"pitcher"))),
+        _ => Box::new(Career::default()),
+    }
+}
+
+/// Takes the seed of the pitcher AI from the query of the URL hash, e.g. `#derby?seed=42`, so
+/// that a logged sequence of pitches can be replayed. Otherwise the seed is taken from the clock.
+fn seed(window: &web_sys::Window) -> u32 {
+    let hash = window.location().hash().unwrap_or_default();
+    let query = hash.split_once('?').map_or("", |(_, query)| query);
+    query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("seed=")?.parse().ok())
+        // the low bits change every millisecond while the time overflows `u32`
+        .unwrap_or_else(|| js_sys::Date::now() as u64 as u32)
+}
+
+/// Falls back to a storage that doesn't persist if `localStorage` is unavailable, e.g. in private
+/// browsing.
+fn storage(window: &web_sys::Window) -> Box<dyn Storage> {
+    match LocalStorage::new(window) {
+        Some(storage) => Box::new(storage),
+        None => {
+            log::log("localStorage is unavailable; progress will not be saved");
+            Box::new(MemoryStorage::new())
+        }
+    }
+}
+
 #[wasm_bindgen]
 pub async fn start() -> Result<(), JsValue> {
     let window = Rc::new(web_sys::window().unwrap());
@@ -78,16 +140,22 @@ pub async fn start() -> Result<(), JsValue> {
     }
 
     let scene_manager = Rc::new(RefCell::new(SceneManager {
-        type_: SceneType::Test,
+        type_: SceneType::Batting,
+        handoff_message: None,
     }));
 
     let shared = SharedContext::new(doc.clone(), ctx.clone());
-    shared
-        .borrow_mut()
-        .init_uniform_buffers(vec!["uniforms_", "camera", "background"])?;
+    shared.borrow_mut().init_uniform_buffers(vec![
+        "uniforms_",
+        "camera",
+        "ui_camera",
+        "background",
+    ])?;
 
     let camera = Rc::new(RefCell::new(camera::CameraController::default()));
-    let input = set_input_handler(canvas.clone());
+    let ui_camera = Rc::new(RefCell::new(camera::UiCameraController::new()));
+    let viewport = watch_viewport(window.clone(), canvas.clone())?;
+    let input = set_input_handler(window.clone(), canvas.clone(), viewport.clone());
 
     let test_uniform = Rc::new(RefCell::new(Uniform {
         size0: 0.01,
@@ -95,33 +163,88 @@ pub async fn start() -> Result<(), JsValue> {
         _pad0: [0, 0],
     }));
 
-    let test_shader = Rc::new(RefCell::new(TestShader::new(ShaderController::new(
-        shared.clone(),
-    ))?));
-    test_shader.borrow_mut().init().await?;
+    let mut test_shader = TestShader::new(ShaderController::new(shared.clone()))?;
+    test_shader.init().await?;
+    let test_shader = Rc::new(RefCell::new(test_shader));
 
     let entity_shader = EntityShader::new(shared.clone())?;
-    entity_shader.borrow_mut().init_textures().await?;
+    EntityShader::init_textures(&entity_shader).await?;
 
     let background_shader = BackgroundShader::new(shared.clone())?;
-    background_shader.borrow_mut().init_textures().await?;
+    BackgroundShader::init_textures(&background_shader).await?;
+
+    let line_shader = LineShader::new(shared.clone())?;
+    let shadow_shader = ShadowShader::new(shared.clone())?;
+
+    let ui_shader = UiShader::new(shared.clone())?;
+    UiShader::init_textures(&ui_shader).await?;
+
+    let font = Rc::new(BitmapFont::load("font.fnt").await?);
+    if font.page != FONT_PAGE {
+        return Err(format!(
+            "font page {} is not loaded; expected {}",
+            font.page, FONT_PAGE
+        )
+        .into());
+    }
+    let text_shader = TextShader::new(shared.clone())?;
+    TextShader::init_textures(&text_shader).await?;
 
     // scene_manager.clone(),
     let batting = Rc::new(RefCell::new(BattingImpl::new(BattingConfigImpl::default())));
     let pitching = Rc::new(RefCell::new(PitchingImpl::new(
         PitchingConfigImpl::default(),
     )));
+    let hit_ball = Rc::new(RefCell::new(HitBallImpl::new(hit_ball::Config::default())));
+    let umpire = Rc::new(RefCell::new(UmpireImpl::new(UmpireConfigImpl::default())));
+    // Log the seed so that the same sequence of pitches can be reproduced.
+    let seed = seed(&window);
+    log::log(&format!(
+        "pitcher seed: {} (replay with #...?seed={})",
+        seed, seed
+    ));
+    let pitcher_ai = Rc::new(RefCell::new(PitcherAiImpl::new(
+        PitcherAiConfigImpl::default(),
+        seed,
+    )));
+    let (save, error) = SaveManager::load(storage(&window));
+    if let Some(error) = error {
+        log::log(&format!("{}; running on the defaults", error.message()));
+    }
     let mut batting_scene = SampleScene::new(
         SampleSceneContext {
             scene_manager: scene_manager.clone(),
             entity_shader: entity_shader.clone(),
             background_shader: background_shader.clone(),
+            line_shader: line_shader.clone(),
+            shadow_shader: shadow_shader.clone(),
+            ui_shader: ui_shader.clone(),
+            text_shader: text_shader.clone(),
+            font: font.clone(),
             camera: camera.clone(),
+            ui_camera: ui_camera.clone(),
             input: input.clone(),
             shared: shared.clone(),
         },
-        BattingSceneGameState::new(batting.clone(), pitching.clone()),
-    );
+        BattingSceneGameState::new(
+            batting.clone(),
+            pitching.clone(),
+            hit_ball.clone(),
+            umpire.clone(),
+            pitcher_ai.clone(),
+        ),
+        game_mode(&window),
+        save,
+    )?;
+    let mut handoff_scene = HandoffScene::new(HandoffSceneContext {
+        scene_manager: scene_manager.clone(),
+        background_shader: background_shader.clone(),
+        text_shader: text_shader.clone(),
+        font: font.clone(),
+        ui_camera: ui_camera.clone(),
+        input: input.clone(),
+        shared: shared.clone(),
+    });
     let test = TestScene::new(TestSceneContext {
         test_shader: test_shader.clone(),
         test_uniform: test_uniform.clone(),
@@ -131,8 +254,9 @@ pub async fn start() -> Result<(), JsValue> {
     ctx.enable(WebGl2RenderingContext::DEPTH_TEST);
     ctx.depth_func(WebGl2RenderingContext::LEQUAL);
     ctx.enable(WebGl2RenderingContext::BLEND);
+    // shaders output premultiplied alpha
     ctx.blend_func(
-        WebGl2RenderingContext::SRC_ALPHA,
+        WebGl2RenderingContext::ONE,
         WebGl2RenderingContext::ONE_MINUS_SRC_ALPHA,
     );
 
@@ -142,14 +266,21 @@ pub async fn start() -> Result<(), JsValue> {
         ctx.clear(
             WebGl2RenderingContext::COLOR_BUFFER_BIT | WebGl2RenderingContext::DEPTH_BUFFER_BIT,
         );
+        {
+            // the cleared bars around the area are left as they are
+            let viewport = viewport.borrow();
+            let [x, y, width, height] = viewport.area;
+            ctx.viewport(x, y, width, height);
+            camera.borrow_mut().projection.aspect_retio = viewport.aspect();
+            let (width, height) = viewport.css_size();
+            ui_camera.borrow_mut().resize(width, height);
+        }
 
-        scene_manager.borrow_mut().type_ = SceneType::Batting;
-        // if (time / 10000.0) % 2. < 1. {
-        // } else {
-        //     scene_manager.borrow_mut().type_ = SceneType::Test;
-        // }
-
-        batting_scene.render(time)?;
+        let type_ = scene_manager.borrow().type_;
+        match type_ {
+            SceneType::Batting => batting_scene.render(time)?,
+            SceneType::Handoff => handoff_scene.render(time)?,
+        }
         input.borrow_mut().resolve();
         Ok(())
     })?;
@@ -0,0 +1,141 @@
+use super::GameMode;
+use crate::game_state::{Difficulty, HitResult, PitchCall, PitchOutcome, Trajectory};
+use crate::save::SaveData;
+
+pub struct Stage {
+    pub pitcher: &'static str,
+    pub difficulty: Difficulty,
+    /// Number of pitches the batter faces in the stage.
+    pub pitches: u32,
+    /// Number of home runs needed to clear the stage.
+    pub home_runs: u32,
+}
+
+/// A sequence of stages, each against a tougher pitcher. Clearing a stage unlocks the next one and
+/// failing it starts the same stage over.
+pub struct Career {
+    stages: Vec<Stage>,
+    /// Index of the furthest stage the player can select.
+    unlocked: usize,
+    current: usize,
+    pitches: u32,
+    home_runs: u32,
+    last_cleared: Option<usize>,
+}
+
+impl Career {
+    pub fn new(stages: Vec<Stage>) -> Self {
+        Self {
+            stages,
+            unlocked: 0,
+            current: 0,
+            pitches: 0,
+            home_runs: 0,
+            last_cleared: None,
+        }
+    }
+
+    fn stage(&self) -> Option<&Stage> {
+        self.stages.get(self.current)
+    }
+
+    fn start_stage(&mut self, index: usize) {
+        self.current = index;
+        self.pitches = 0;
+        self.home_runs = 0;
+    }
+}
+
+impl GameMode for Career {
+    /// Resumes from the furthest stage unlocked.
+    fn load(&mut self, data: &SaveData) {
+        self.unlocked = data
+            .career_unlocked
+            .min(self.stages.len().saturating_sub(1));
+        self.start_stage(self.unlocked);
+    }
+
+    fn store(&self, data: &mut SaveData) {
+        data.career_unlocked = data.career_unlocked.max(self.unlocked);
+    }
+
+    fn difficulty(&self) -> Difficulty {
+        self.stage()
+            .map(|stage| stage.difficulty)
+            .unwrap_or(Difficulty::Hard)
+    }
+
+    fn on_outcome(&mut self, outcome: &PitchOutcome, _: Option<&Trajectory>) {
+        let (pitches, home_runs) = match self.stage() {
+            Some(stage) => (stage.pitches, stage.home_runs),
+            None => return,
+        };
+        self.pitches += 1;
+        if let PitchCall::InPlay(HitResult::HomeRun) = outcome.call {
+            self.home_runs += 1;
+        }
+
+        if self.home_runs >= home_runs {
+            let next = self.current + 1;
+            self.last_cleared = Some(self.current);
+            self.unlocked = self.unlocked.max(next.min(self.stages.len() - 1));
+            self.start_stage(next);
+        } else if self.pitches >= pitches {
+            self.last_cleared = None;
+            self.start_stage(self.current);
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        self.current >= self.stages.len()
+    }
+
+    fn status(&self) -> String {
+        let stage = match self.stage() {
+            Some(stage) => stage,
+            None => return String::from("Career cleared!"),
+        };
+        let cleared = match self.last_cleared {
+            Some(index) if self.pitches == 0 => {
+                format!("Beat {}! ", self.stages[index].pitcher)
+            }
+            _ => String::new(),
+        };
+        format!(
+            "{}Stage {} vs {}: HR {}/{}, pitches left {}",
+            cleared,
+            self.current + 1,
+            stage.pitcher,
+            self.home_runs,
+            stage.home_runs,
+            stage.pitches - self.pitches,
+        )
+    }
+}
+
+// Concrete Instances
+
+impl Career {
+    pub fn default() -> Self {
+        Self::new(vec![
+            Stage {
+                pitcher: "Rookie",
+                difficulty: Difficulty::Easy,
+                pitches: 10,
+                home_runs: 1,
+            },
+            Stage {
+                pitcher: "Veteran",
+                difficulty: Difficulty::Normal,
+                pitches: 12,
+                home_runs: 2,
+            },
+            Stage {
+                pitcher: "Ace",
+                difficulty: Difficulty::Hard,
+                pitches: 15,
+                home_runs: 3,
+            },
+        ])
+    }
+}
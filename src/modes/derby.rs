@@ -0,0 +1,159 @@
+use super::GameMode;
+use crate::game_state::{Difficulty, HitResult, PitchCall, PitchOutcome, Trajectory};
+use crate::save::SaveData;
+
+/// Home runs of a round with their landing distances.
+pub struct RoundResult {
+    pub distances: Vec<f32>,
+}
+
+/// Each round lasts until the batter makes a given number of outs. Every swing that doesn't end up
+/// as a home run is an out, while taken pitches are free.
+pub struct Derby {
+    difficulty: Difficulty,
+    rounds: usize,
+    outs_per_round: u32,
+    leaderboard_size: usize,
+    outs: u32,
+    current: RoundResult,
+    finished: Vec<RoundResult>,
+    /// Longest home runs ever in descending order.
+    leaderboard: Vec<f32>,
+}
+
+impl RoundResult {
+    fn longest(&self) -> f32 {
+        self.distances.iter().cloned().fold(0., f32::max)
+    }
+}
+
+impl Derby {
+    pub fn new(
+        difficulty: Difficulty,
+        rounds: usize,
+        outs_per_round: u32,
+        leaderboard_size: usize,
+    ) -> Self {
+        Self {
+            difficulty,
+            rounds,
+            outs_per_round,
+            leaderboard_size,
+            outs: 0,
+            current: RoundResult { distances: vec![] },
+            finished: vec![],
+            leaderboard: vec![],
+        }
+    }
+
+    fn record(&mut self, distance: f32) {
+        self.current.distances.push(distance);
+        let index = self
+            .leaderboard
+            .iter()
+            .position(|&d| d < distance)
+            .unwrap_or(self.leaderboard.len());
+        self.leaderboard.insert(index, distance);
+        self.leaderboard.truncate(self.leaderboard_size);
+    }
+
+    fn round_summary(&self, index: usize) -> String {
+        let round = &self.finished[index];
+        format!(
+            "Round {}: {} HR, longest {:.1}",
+            index + 1,
+            round.distances.len(),
+            round.longest(),
+        )
+    }
+
+    fn session_summary(&self) -> String {
+        let total: usize = self
+            .finished
+            .iter()
+            .map(|round| round.distances.len())
+            .sum();
+        let leaderboard = self
+            .leaderboard
+            .iter()
+            .enumerate()
+            .map(|(i, distance)| format!("{}. {:.1}", i + 1, distance))
+            .collect::<Vec<_>>()
+            .join("<br>");
+        format!("Derby over: {} HR<br>{}", total, leaderboard)
+    }
+}
+
+impl GameMode for Derby {
+    fn load(&mut self, data: &SaveData) {
+        self.leaderboard = data.high_scores.clone();
+        self.leaderboard
+            .sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+        self.leaderboard.truncate(self.leaderboard_size);
+    }
+
+    fn store(&self, data: &mut SaveData) {
+        data.high_scores = self.leaderboard.clone();
+    }
+
+    fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    fn on_outcome(&mut self, outcome: &PitchOutcome, trajectory: Option<&Trajectory>) {
+        if self.is_over() {
+            return;
+        }
+        match (&outcome.call, trajectory) {
+            (PitchCall::InPlay(HitResult::HomeRun), Some(trajectory)) => {
+                self.record(trajectory.distance)
+            }
+            (PitchCall::Ball, _) | (PitchCall::CalledStrike, _) => {}
+            _ => self.outs += 1,
+        }
+
+        if self.outs >= self.outs_per_round {
+            let round = std::mem::replace(&mut self.current, RoundResult { distances: vec![] });
+            self.finished.push(round);
+            self.outs = 0;
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        self.finished.len() >= self.rounds
+    }
+
+    fn status(&self) -> String {
+        if self.is_over() {
+            let rounds = (0..self.finished.len())
+                .map(|index| self.round_summary(index))
+                .collect::<Vec<_>>()
+                .join("<br>");
+            return format!("{}<br>{}", rounds, self.session_summary());
+        }
+        let summary = match self.finished.len() {
+            0 => String::new(),
+            n if self.outs == 0 && self.current.distances.is_empty() => {
+                format!("{}<br>", self.round_summary(n - 1))
+            }
+            _ => String::new(),
+        };
+        format!(
+            "{}Round {}/{}: HR {}, outs {}/{}",
+            summary,
+            self.finished.len() + 1,
+            self.rounds,
+            self.current.distances.len(),
+            self.outs,
+            self.outs_per_round,
+        )
+    }
+}
+
+// Concrete Instances
+
+impl Derby {
+    pub fn default() -> Self {
+        Self::new(Difficulty::Easy, 3, 10, 5)
+    }
+}
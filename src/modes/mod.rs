@@ -0,0 +1,64 @@
+mod career;
+mod derby;
+mod practice;
+mod versus;
+
+pub use career::Career;
+pub use derby::Derby;
+pub use practice::Practice;
+pub use versus::Versus;
+
+use crate::game_state::{Difficulty, HitInfo, Pitch, PitchOutcome, Trajectory};
+use crate::save::SaveData;
+
+/// State of a game of innings, as shown on the scoreboard.
+#[derive(Clone, Copy)]
+pub struct Scoreboard {
+    pub inning: u32,
+    pub is_bottom: bool,
+    pub outs: u32,
+    /// Runs of the first and the second player.
+    pub runs: [u32; 2],
+}
+
+/// Rules of a session played on top of the batting scene.
+pub trait GameMode {
+    /// Restores the progress of the mode from the save data.
+    fn load(&mut self, _data: &SaveData) {}
+    /// Writes the progress of the mode into the save data.
+    fn store(&self, _data: &mut SaveData) {}
+    fn difficulty(&self) -> Difficulty;
+    /// Overrides the pitcher AI with a given pitch.
+    fn next_pitch(&mut self) -> Option<Pitch> {
+        None
+    }
+    fn on_contact(&mut self, _info: &HitInfo) {}
+    fn on_key(&mut self, _key: &str) {}
+    /// Called once for every pitch that has been called by the umpire. `trajectory` is the batted
+    /// ball of the pitch if any.
+    fn on_outcome(&mut self, outcome: &PitchOutcome, trajectory: Option<&Trajectory>);
+    /// No more pitches are thrown once the mode is over.
+    fn is_over(&self) -> bool;
+    /// Message to show while the machine is passed to the next player, if it should be. It is
+    /// plain text with lines separated by `\n`.
+    fn take_handoff(&mut self) -> Option<String> {
+        None
+    }
+    /// Whether balls and strikes are shown.
+    fn keeps_count(&self) -> bool {
+        true
+    }
+    /// Recent contact points in the bat's coordinate, shown over the bat.
+    fn contact_points(&self) -> Vec<[f32; 2]> {
+        vec![]
+    }
+    /// Whether the paths of the last pitch and batted ball are drawn.
+    fn shows_paths(&self) -> bool {
+        false
+    }
+    /// Inning, outs and score if the mode plays innings.
+    fn scoreboard(&self) -> Option<Scoreboard> {
+        None
+    }
+    fn status(&self) -> String;
+}
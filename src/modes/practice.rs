@@ -0,0 +1,148 @@
+use super::GameMode;
+use crate::game_state::{
+    ContactGrade, Difficulty, HitInfo, Pitch, PitchKind, PitchOutcome, Trajectory,
+};
+
+use std::collections::VecDeque;
+
+/// Key to show or hide the paths of the last pitch and batted ball.
+const PATHS_KEY: &str = "p";
+
+/// Pitch thrown over and over by the pitch machine.
+pub struct PitchMachine {
+    pub kind: PitchKind,
+    pub speed: f32,
+    pub target: [f32; 2],
+}
+
+pub struct ContactRecord {
+    /// Where the ball met the bat, in the bat's coordinate.
+    pub meet: [f32; 2],
+    /// Milliseconds from the ideal timing. Negative is early and positive is late.
+    pub timing: f32,
+    pub grade: ContactGrade,
+}
+
+/// Replays the same pitch without counting balls, strikes or outs, keeping the last contacts for
+/// review.
+pub struct Practice {
+    machine: PitchMachine,
+    history_size: usize,
+    history: VecDeque<ContactRecord>,
+    is_showing_paths: bool,
+}
+
+impl PitchMachine {
+    /// Overrides the settings with `[kind, speed, target x, target y]`, skipping invalid ones.
+    pub fn configure(&mut self, args: &[&str]) {
+        let kind = match args.first().copied() {
+            Some("fastball") => Some(PitchKind::Fastball),
+            Some("curveball") => Some(PitchKind::Curveball),
+            Some("slider") => Some(PitchKind::Slider),
+            Some("changeup") => Some(PitchKind::Changeup),
+            _ => None,
+        };
+        let number = |index: usize| args.get(index).and_then(|arg| arg.parse::<f32>().ok());
+        self.kind = kind.unwrap_or(self.kind);
+        self.speed = number(1).unwrap_or(self.speed);
+        self.target = [
+            number(2).unwrap_or(self.target[0]),
+            number(3).unwrap_or(self.target[1]),
+        ];
+    }
+}
+
+impl Practice {
+    pub fn new(machine: PitchMachine, history_size: usize) -> Self {
+        Self {
+            machine,
+            history_size,
+            history: VecDeque::with_capacity(history_size),
+            is_showing_paths: true,
+        }
+    }
+
+    pub fn machine_mut(&mut self) -> &mut PitchMachine {
+        &mut self.machine
+    }
+}
+
+impl GameMode for Practice {
+    fn difficulty(&self) -> Difficulty {
+        Difficulty::Normal
+    }
+
+    fn next_pitch(&mut self) -> Option<Pitch> {
+        Some(Pitch {
+            kind: self.machine.kind,
+            target: self.machine.target,
+            speed: self.machine.speed,
+        })
+    }
+
+    fn on_contact(&mut self, info: &HitInfo) {
+        if self.history.len() >= self.history_size {
+            self.history.pop_front();
+        }
+        self.history.push_back(ContactRecord {
+            meet: info.meet_position,
+            timing: info.quality.timing,
+            grade: info.quality.grade,
+        });
+    }
+
+    fn on_key(&mut self, key: &str) {
+        if key.to_lowercase() == PATHS_KEY {
+            self.is_showing_paths = !self.is_showing_paths;
+        }
+    }
+
+    fn on_outcome(&mut self, _: &PitchOutcome, _: Option<&Trajectory>) {}
+
+    fn is_over(&self) -> bool {
+        false
+    }
+
+    fn keeps_count(&self) -> bool {
+        false
+    }
+
+    fn contact_points(&self) -> Vec<[f32; 2]> {
+        self.history.iter().map(|record| record.meet).collect()
+    }
+
+    fn shows_paths(&self) -> bool {
+        self.is_showing_paths
+    }
+
+    fn status(&self) -> String {
+        let timings = self
+            .history
+            .iter()
+            .rev()
+            .map(|record| format!("{} {:+.0}ms", record.grade.label(), record.timing))
+            .collect::<Vec<_>>()
+            .join("<br>");
+        format!(
+            "Practice: {} x{:.2}<br>{}",
+            self.machine.kind.label(),
+            self.machine.speed,
+            timings
+        )
+    }
+}
+
+// Concrete Instances
+
+impl Practice {
+    pub fn default() -> Self {
+        Self::new(
+            PitchMachine {
+                kind: PitchKind::Fastball,
+                speed: 0.9,
+                target: [0., 0.],
+            },
+            8,
+        )
+    }
+}
@@ -0,0 +1,368 @@
+use super::{GameMode, Scoreboard};
+use crate::game_state::{
+    AtBatResult, Difficulty, HitResult, Pitch, PitchKind, PitchOutcome, Trajectory,
+};
+
+const PITCH_KEYS: [(&str, PitchKind); 4] = [
+    ("1", PitchKind::Fastball),
+    ("2", PitchKind::Curveball),
+    ("3", PitchKind::Slider),
+    ("4", PitchKind::Changeup),
+];
+/// Keys laid out as a 3x3 grid over the strike zone, seen from the batter.
+const TARGET_KEYS: [(&str, [f32; 2]); 9] = [
+    ("q", [-0.2, 0.25]),
+    ("w", [0., 0.25]),
+    ("e", [0.2, 0.25]),
+    ("a", [-0.2, 0.]),
+    ("s", [0., 0.]),
+    ("d", [0.2, 0.]),
+    ("z", [-0.2, -0.25]),
+    ("x", [0., -0.25]),
+    ("c", [0.2, -0.25]),
+];
+
+#[derive(Default)]
+pub struct PlayerScore {
+    pub runs: u32,
+    pub hits: u32,
+    pub home_runs: u32,
+}
+
+/// Two players take turns at bat on the same machine. The first player bats in the top half of
+/// each inning. Batted balls are simplified into singles and home runs: a single moves every runner
+/// up a base and a walk only moves the forced ones.
+pub struct Versus {
+    difficulty: Difficulty,
+    innings: u32,
+    outs_per_half: u32,
+    /// Lets the fielding player choose pitches with the keyboard instead of the pitcher AI.
+    pitch_control: bool,
+    inning: u32,
+    is_bottom: bool,
+    outs: u32,
+    bases: [bool; 3],
+    scores: [PlayerScore; 2],
+    selected_pitch: Option<Pitch>,
+    handoff: Option<String>,
+    is_over: bool,
+}
+
+impl Versus {
+    pub fn new(
+        difficulty: Difficulty,
+        innings: u32,
+        outs_per_half: u32,
+        pitch_control: bool,
+    ) -> Self {
+        Self {
+            difficulty,
+            innings,
+            outs_per_half,
+            pitch_control,
+            inning: 1,
+            is_bottom: false,
+            outs: 0,
+            bases: [false; 3],
+            scores: [PlayerScore::default(), PlayerScore::default()],
+            selected_pitch: None,
+            handoff: None,
+            is_over: false,
+        }
+    }
+
+    fn batter(&self) -> usize {
+        self.is_bottom as usize
+    }
+
+    fn advance_runners(&mut self, walk: bool) -> u32 {
+        let [first, second, third] = self.bases;
+        if walk {
+            let runs = (first && second && third) as u32;
+            self.bases = [true, first || second, third || first && second];
+            return runs;
+        }
+        self.bases = [true, first, second];
+        third as u32
+    }
+
+    fn home_run(&mut self) -> u32 {
+        let runners = self.bases.iter().filter(|&&base| base).count() as u32;
+        self.bases = [false; 3];
+        runners + 1
+    }
+
+    fn end_half(&mut self) {
+        self.outs = 0;
+        self.bases = [false; 3];
+        self.selected_pitch = None;
+        let last_inning = self.inning >= self.innings;
+        let [first, second] = [self.scores[0].runs, self.scores[1].runs];
+        if self.is_bottom {
+            if last_inning && first != second {
+                self.is_over = true;
+            } else {
+                self.inning += 1;
+                self.is_bottom = false;
+            }
+        } else if last_inning && second > first {
+            // the second player doesn't need to bat
+            self.is_over = true;
+        } else {
+            self.is_bottom = true;
+        }
+
+        self.handoff = Some(if self.is_over {
+            self.result()
+        } else {
+            format!(
+                "{} of inning {}: Player {} to bat",
+                if self.is_bottom { "Bottom" } else { "Top" },
+                self.inning,
+                self.batter() + 1,
+            )
+        });
+    }
+
+    fn result(&self) -> String {
+        let [first, second] = [self.scores[0].runs, self.scores[1].runs];
+        let winner = if first > second {
+            "Player 1 wins"
+        } else if second > first {
+            "Player 2 wins"
+        } else {
+            "Draw"
+        };
+        let line = |score: &PlayerScore| {
+            format!(
+                "{} R / {} H / {} HR",
+                score.runs, score.hits, score.home_runs
+            )
+        };
+        format!(
+            "{} {}-{}\nP1: {}\nP2: {}",
+            winner,
+            first,
+            second,
+            line(&self.scores[0]),
+            line(&self.scores[1]),
+        )
+    }
+}
+
+impl GameMode for Versus {
+    fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    fn next_pitch(&mut self) -> Option<Pitch> {
+        self.selected_pitch.take()
+    }
+
+    fn on_key(&mut self, key: &str) {
+        if !self.pitch_control {
+            return;
+        }
+        let key = key.to_lowercase();
+        let kind = PITCH_KEYS
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, kind)| *kind);
+        let target = TARGET_KEYS
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, target)| *target);
+        if kind.is_none() && target.is_none() {
+            return;
+        }
+        let pitch = self.selected_pitch.get_or_insert(Pitch {
+            kind: PitchKind::Fastball,
+            target: [0., 0.],
+            speed: 1.,
+        });
+        pitch.kind = kind.unwrap_or(pitch.kind);
+        pitch.target = target.unwrap_or(pitch.target);
+    }
+
+    fn on_outcome(&mut self, outcome: &PitchOutcome, _: Option<&Trajectory>) {
+        if self.is_over {
+            return;
+        }
+        let runs = match &outcome.at_bat {
+            None => return,
+            Some(AtBatResult::Strikeout) => {
+                self.outs += 1;
+                0
+            }
+            Some(AtBatResult::Walk) => self.advance_runners(true),
+            Some(AtBatResult::InPlay(result)) => {
+                let batter = self.batter();
+                let score = &mut self.scores[batter];
+                score.hits += 1;
+                match result {
+                    HitResult::HomeRun => {
+                        score.home_runs += 1;
+                        self.home_run()
+                    }
+                    _ => self.advance_runners(false),
+                }
+            }
+        };
+        let batter = self.batter();
+        self.scores[batter].runs += runs;
+
+        let is_walk_off = self.is_bottom
+            && self.inning >= self.innings
+            && self.scores[1].runs > self.scores[0].runs;
+        if is_walk_off {
+            self.is_over = true;
+            self.handoff = Some(self.result());
+        } else if self.outs >= self.outs_per_half {
+            self.end_half();
+        }
+    }
+
+    fn take_handoff(&mut self) -> Option<String> {
+        self.handoff.take()
+    }
+
+    fn is_over(&self) -> bool {
+        self.is_over
+    }
+
+    fn scoreboard(&self) -> Option<Scoreboard> {
+        Some(Scoreboard {
+            inning: self.inning,
+            is_bottom: self.is_bottom,
+            outs: self.outs,
+            runs: [self.scores[0].runs, self.scores[1].runs],
+        })
+    }
+
+    fn status(&self) -> String {
+        if self.is_over {
+            return self.result().replace('\n', "<br>");
+        }
+        let bases = self
+            .bases
+            .iter()
+            .map(|&base| if base { "◆" } else { "◇" })
+            .collect::<String>();
+        let pitch = match &self.selected_pitch {
+            Some(pitch) => format!("<br>Next: {}", pitch.kind.label()),
+            None => String::new(),
+        };
+        format!(
+            "{} {}: P1 {} - P2 {}, {} out, {}{}",
+            if self.is_bottom { "Bot" } else { "Top" },
+            self.inning,
+            self.scores[0].runs,
+            self.scores[1].runs,
+            self.outs,
+            bases,
+            pitch,
+        )
+    }
+}
+
+// Concrete Instances
+
+impl Versus {
+    pub fn default(pitch_control: bool) -> Self {
+        Self::new(Difficulty::Normal, 3, 3, pitch_control)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_state::{Count, PitchCall};
+
+    fn play(versus: &mut Versus, at_bat: AtBatResult) {
+        let call = match &at_bat {
+            AtBatResult::Walk => PitchCall::Ball,
+            AtBatResult::Strikeout => PitchCall::SwingingStrike,
+            AtBatResult::InPlay(result) => PitchCall::InPlay(result.clone()),
+        };
+        versus.on_outcome(
+            &PitchOutcome {
+                call,
+                count: Count::default(),
+                at_bat: Some(at_bat),
+            },
+            None,
+        );
+    }
+
+    fn strike_out_side(versus: &mut Versus) {
+        for _ in 0..versus.outs_per_half {
+            play(versus, AtBatResult::Strikeout);
+        }
+    }
+
+    /// Runs scored and bases after `at_bat` with runners on `bases` in the top of the first.
+    fn advance(bases: [bool; 3], at_bat: AtBatResult) -> (u32, [bool; 3]) {
+        let mut versus = Versus::default(false);
+        versus.bases = bases;
+        play(&mut versus, at_bat);
+        (versus.scores[0].runs, versus.bases)
+    }
+
+    #[test]
+    fn walk_moves_only_forced_runners() {
+        let walk = || AtBatResult::Walk;
+        assert_eq!(advance([true; 3], walk()), (1, [true; 3]));
+        assert_eq!(advance([true, false, true], walk()), (0, [true; 3]));
+        assert_eq!(
+            advance([false, true, false], walk()),
+            (0, [true, true, false])
+        );
+    }
+
+    #[test]
+    fn single_moves_every_runner() {
+        let single = || AtBatResult::InPlay(HitResult::SafeHit);
+        assert_eq!(advance([true; 3], single()), (1, [true; 3]));
+        assert_eq!(
+            advance([true, false, true], single()),
+            (1, [true, true, false])
+        );
+        assert_eq!(
+            advance([false, true, false], single()),
+            (0, [true, false, true])
+        );
+    }
+
+    #[test]
+    fn home_run_in_the_bottom_of_the_last_inning_walks_off() {
+        let mut versus = Versus::new(Difficulty::Normal, 1, 3, false);
+        strike_out_side(&mut versus);
+        assert_eq!(
+            versus.take_handoff().as_deref(),
+            Some("Bottom of inning 1: Player 2 to bat")
+        );
+
+        play(&mut versus, AtBatResult::InPlay(HitResult::HomeRun));
+        assert!(versus.is_over());
+        assert_eq!(versus.outs, 0);
+        let handoff = versus.take_handoff().unwrap_or_default();
+        assert!(handoff.starts_with("Player 2 wins 0-1\n"));
+    }
+
+    #[test]
+    fn tie_after_the_last_inning_goes_to_an_extra_inning() {
+        let mut versus = Versus::new(Difficulty::Normal, 1, 3, false);
+        play(&mut versus, AtBatResult::InPlay(HitResult::HomeRun));
+        strike_out_side(&mut versus);
+        play(&mut versus, AtBatResult::InPlay(HitResult::HomeRun));
+        assert!(!versus.is_over());
+        strike_out_side(&mut versus);
+
+        assert!(!versus.is_over());
+        assert_eq!((versus.inning, versus.is_bottom), (2, false));
+        assert_eq!(
+            versus.take_handoff().as_deref(),
+            Some("Top of inning 2: Player 1 to bat")
+        );
+    }
+}
@@ -0,0 +1,189 @@
+mod schema;
+mod storage;
+
+pub use schema::DecodeError;
+pub use storage::{LocalStorage, MemoryStorage, Storage};
+
+use crate::input::SwingBindings;
+use crate::stats::StatTotals;
+
+const SAVE_KEY: &str = "kassie.save";
+/// Unreadable save data is moved here instead of being overwritten so that it can be recovered by
+/// hand.
+const CORRUPT_KEY: &str = "kassie.save.corrupt";
+
+pub struct Settings {
+    pub bindings: SwingBindings,
+    /// Milliseconds subtracted from input timestamps to make up for the latency of the device.
+    pub calibration_offset: f32,
+    /// Master volume in `[0, 1]`.
+    pub volume: f32,
+    pub depth_cues: DepthCues,
+}
+
+/// Extra cues drawn around the ball to judge its depth.
+#[derive(Clone, Copy, PartialEq)]
+pub struct DepthCues {
+    /// Blob shadow on the ground under the ball.
+    pub shadow: bool,
+    /// Exaggerates the growth of the ball as it comes closer to the camera.
+    pub scale_with_distance: bool,
+    /// Fading copies of the ball along its recent positions.
+    pub trail: bool,
+}
+
+pub struct SaveData {
+    pub settings: Settings,
+    /// Index of the furthest career stage unlocked.
+    pub career_unlocked: usize,
+    /// Longest home-run distances of the derby in descending order.
+    pub high_scores: Vec<f32>,
+    /// Stats accumulated over all sessions.
+    pub stats: StatTotals,
+}
+
+pub struct SaveManager {
+    storage: Box<dyn Storage>,
+    data: SaveData,
+    /// Set when the stored data was written by a newer build, which must not be overwritten.
+    is_read_only: bool,
+}
+
+impl SaveData {
+    pub fn default() -> Self {
+        Self {
+            settings: Settings {
+                bindings: SwingBindings::default(),
+                calibration_offset: 0.,
+                volume: 0.8,
+                depth_cues: DepthCues {
+                    shadow: true,
+                    scale_with_distance: false,
+                    trail: true,
+                },
+            },
+            career_unlocked: 0,
+            high_scores: vec![],
+            stats: StatTotals::default(),
+        }
+    }
+}
+
+impl SaveManager {
+    /// Loads the save data from the storage, falling back to the defaults if there is none or it is
+    /// unreadable. The error is returned along with the manager if the data couldn't be used.
+    ///
+    /// Corrupt data is moved aside and replaced on the next save. Data of a newer build is kept
+    /// as it is, and the session runs on the defaults without saving.
+    pub fn load(mut storage: Box<dyn Storage>) -> (Self, Option<DecodeError>) {
+        let (data, is_read_only, error) = match storage
+            .get(SAVE_KEY)
+            .map(|text| (schema::decode(&text), text))
+        {
+            None => (SaveData::default(), false, None),
+            Some((Ok(data), _)) => (data, false, None),
+            Some((Err(error @ DecodeError::UnknownVersion(_)), _)) => {
+                (SaveData::default(), true, Some(error))
+            }
+            Some((Err(error), text)) => {
+                storage.set(CORRUPT_KEY, &text);
+                storage.remove(SAVE_KEY);
+                (SaveData::default(), false, Some(error))
+            }
+        };
+        let manager = Self {
+            storage,
+            data,
+            is_read_only,
+        };
+        (manager, error)
+    }
+
+    pub fn data(&self) -> &SaveData {
+        &self.data
+    }
+
+    pub fn data_mut(&mut self) -> &mut SaveData {
+        &mut self.data
+    }
+
+    pub fn save(&mut self) {
+        if !self.is_read_only {
+            self.storage.set(SAVE_KEY, &schema::encode(&self.data));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Lets the test look into the storage after handing it to the manager.
+    #[derive(Clone)]
+    struct SharedStorage(Rc<RefCell<MemoryStorage>>);
+
+    impl Storage for SharedStorage {
+        fn get(&self, key: &str) -> Option<String> {
+            self.0.borrow().get(key)
+        }
+        fn set(&mut self, key: &str, value: &str) {
+            self.0.borrow_mut().set(key, value);
+        }
+        fn remove(&mut self, key: &str) {
+            self.0.borrow_mut().remove(key);
+        }
+    }
+
+    fn storage_with(save: Option<&str>) -> SharedStorage {
+        let mut storage = MemoryStorage::new();
+        if let Some(save) = save {
+            storage.set(SAVE_KEY, save);
+        }
+        SharedStorage(Rc::new(RefCell::new(storage)))
+    }
+
+    #[test]
+    fn saved_data_is_loaded_back() {
+        let storage = storage_with(None);
+        let (mut manager, error) = SaveManager::load(Box::new(storage.clone()));
+        assert!(error.is_none());
+        manager.data_mut().career_unlocked = 3;
+        manager.save();
+
+        let (manager, error) = SaveManager::load(Box::new(storage));
+        assert!(error.is_none());
+        assert_eq!(manager.data().career_unlocked, 3);
+    }
+
+    #[test]
+    fn corrupt_data_is_moved_aside() {
+        let storage = storage_with(Some("garbage"));
+        let (mut manager, error) = SaveManager::load(Box::new(storage.clone()));
+        assert!(matches!(error, Some(DecodeError::MissingVersion)));
+        assert_eq!(manager.data().career_unlocked, 0);
+        assert_eq!(storage.get(CORRUPT_KEY).as_deref(), Some("garbage"));
+        assert!(storage.get(SAVE_KEY).is_none());
+
+        manager.save();
+        assert!(storage.get(SAVE_KEY).is_some());
+    }
+
+    #[test]
+    fn data_of_a_newer_build_is_kept() {
+        let newer = format!(
+            "version={}\ncareer.unlocked=5\n",
+            schema::CURRENT_VERSION + 1
+        );
+        let storage = storage_with(Some(&newer));
+        let (mut manager, error) = SaveManager::load(Box::new(storage.clone()));
+        assert!(matches!(error, Some(DecodeError::UnknownVersion(_))));
+        assert_eq!(manager.data().career_unlocked, 0);
+
+        manager.save();
+        assert_eq!(storage.get(SAVE_KEY), Some(newer));
+        assert!(storage.get(CORRUPT_KEY).is_none());
+    }
+}
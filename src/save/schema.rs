@@ -0,0 +1,273 @@
+//! Text format of the save data. The first line is `version=N` followed by a `key=value` line for
+//! each field. Unknown keys are ignored and invalid or missing values fall back to the defaults,
+//! so that adding a field doesn't need a new version.
+
+use super::{DepthCues, SaveData, Settings};
+use crate::input::{Modifier, SwingBindings};
+use crate::stats::StatTotals;
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+pub const CURRENT_VERSION: u32 = 1;
+
+type Fields = BTreeMap<String, String>;
+
+/// `MIGRATIONS[n]` upgrades the fields of version `n + 1` to version `n + 2`. Append one whenever a
+/// field changes its meaning and bump `CURRENT_VERSION`.
+const MIGRATIONS: [fn(&mut Fields); (CURRENT_VERSION - 1) as usize] = [];
+
+pub enum DecodeError {
+    MissingVersion,
+    /// Written by a newer build, which this build can't read without losing data.
+    UnknownVersion(u32),
+}
+
+impl DecodeError {
+    pub fn message(&self) -> String {
+        match self {
+            DecodeError::MissingVersion => String::from("save data is corrupt"),
+            DecodeError::UnknownVersion(version) => {
+                format!("unknown save data version: {}", version)
+            }
+        }
+    }
+}
+
+pub fn encode(data: &SaveData) -> String {
+    let Settings {
+        bindings,
+        calibration_offset,
+        volume,
+        depth_cues,
+    } = &data.settings;
+    let stats = &data.stats;
+    let high_scores = data
+        .high_scores
+        .iter()
+        .map(|score| score.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let fields: [(&str, String); 17] = [
+        ("settings.bindings.power", bindings.power.name().to_string()),
+        (
+            "settings.bindings.contact",
+            bindings.contact.name().to_string(),
+        ),
+        ("settings.bindings.bunt", bindings.bunt.name().to_string()),
+        (
+            "settings.calibration_offset",
+            calibration_offset.to_string(),
+        ),
+        ("settings.volume", volume.to_string()),
+        ("settings.depth_cues.shadow", depth_cues.shadow.to_string()),
+        (
+            "settings.depth_cues.scale_with_distance",
+            depth_cues.scale_with_distance.to_string(),
+        ),
+        ("settings.depth_cues.trail", depth_cues.trail.to_string()),
+        ("career.unlocked", data.career_unlocked.to_string()),
+        ("derby.high_scores", high_scores),
+        ("stats.pitches", stats.pitches.to_string()),
+        ("stats.swings", stats.swings.to_string()),
+        ("stats.whiffs", stats.whiffs.to_string()),
+        (
+            "stats.plate_appearances",
+            stats.plate_appearances.to_string(),
+        ),
+        ("stats.at_bats", stats.at_bats.to_string()),
+        ("stats.hits", stats.hits.to_string()),
+        ("stats.home_runs", stats.home_runs.to_string()),
+    ];
+    let mut text = format!("version={}\n", CURRENT_VERSION);
+    for (key, value) in fields.iter() {
+        text.push_str(&format!("{}={}\n", key, value));
+    }
+    text
+}
+
+pub fn decode(text: &str) -> Result<SaveData, DecodeError> {
+    let mut lines = text.lines();
+    let version = lines
+        .next()
+        .and_then(|line| line.strip_prefix("version="))
+        .and_then(|version| version.trim().parse::<u32>().ok())
+        .ok_or(DecodeError::MissingVersion)?;
+    if version == 0 || CURRENT_VERSION < version {
+        return Err(DecodeError::UnknownVersion(version));
+    }
+
+    let mut fields = lines
+        .filter_map(|line| {
+            let mut pair = line.splitn(2, '=');
+            Some((
+                pair.next()?.trim().to_string(),
+                pair.next()?.trim().to_string(),
+            ))
+        })
+        .collect::<Fields>();
+    migrate(&mut fields, version, &MIGRATIONS);
+
+    let default = SaveData::default();
+    let bindings = &default.settings.bindings;
+    let depth_cues = &default.settings.depth_cues;
+    let stats = &default.stats;
+    Ok(SaveData {
+        settings: Settings {
+            bindings: SwingBindings {
+                power: modifier(&fields, "settings.bindings.power", bindings.power),
+                contact: modifier(&fields, "settings.bindings.contact", bindings.contact),
+                bunt: modifier(&fields, "settings.bindings.bunt", bindings.bunt),
+            },
+            calibration_offset: number(
+                &fields,
+                "settings.calibration_offset",
+                default.settings.calibration_offset,
+            ),
+            volume: number(&fields, "settings.volume", default.settings.volume).clamp(0., 1.),
+            depth_cues: DepthCues {
+                shadow: value(&fields, "settings.depth_cues.shadow", depth_cues.shadow),
+                scale_with_distance: value(
+                    &fields,
+                    "settings.depth_cues.scale_with_distance",
+                    depth_cues.scale_with_distance,
+                ),
+                trail: value(&fields, "settings.depth_cues.trail", depth_cues.trail),
+            },
+        },
+        career_unlocked: value(&fields, "career.unlocked", default.career_unlocked),
+        high_scores: fields
+            .get("derby.high_scores")
+            .map(|scores| {
+                scores
+                    .split(',')
+                    .filter_map(|score| score.parse::<f32>().ok())
+                    .filter(|score| score.is_finite())
+                    .collect()
+            })
+            .unwrap_or(default.high_scores),
+        stats: StatTotals {
+            pitches: value(&fields, "stats.pitches", stats.pitches),
+            swings: value(&fields, "stats.swings", stats.swings),
+            whiffs: value(&fields, "stats.whiffs", stats.whiffs),
+            plate_appearances: value(&fields, "stats.plate_appearances", stats.plate_appearances),
+            at_bats: value(&fields, "stats.at_bats", stats.at_bats),
+            hits: value(&fields, "stats.hits", stats.hits),
+            home_runs: value(&fields, "stats.home_runs", stats.home_runs),
+        },
+    })
+}
+
+/// Upgrades `fields` of `version` to the current version with the migrations from that version on.
+fn migrate(fields: &mut Fields, version: u32, migrations: &[fn(&mut Fields)]) {
+    for migrate in &migrations[(version - 1) as usize..] {
+        migrate(fields);
+    }
+}
+
+fn value<T: FromStr>(fields: &Fields, key: &str, default: T) -> T {
+    fields
+        .get(key)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+fn number(fields: &Fields, key: &str, default: f32) -> f32 {
+    Some(value(fields, key, default))
+        .filter(|value| value.is_finite())
+        .unwrap_or(default)
+}
+
+fn modifier(fields: &Fields, key: &str, default: Modifier) -> Modifier {
+    fields
+        .get(key)
+        .and_then(|name| Modifier::from_name(name))
+        .unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_keeps_every_field() {
+        let mut data = SaveData::default();
+        data.settings.bindings.power = Modifier::Ctrl;
+        data.settings.calibration_offset = 12.5;
+        data.settings.volume = 0.3;
+        data.settings.depth_cues.scale_with_distance = true;
+        data.career_unlocked = 2;
+        data.high_scores = vec![31.5, 20.];
+        data.stats.hits = 7;
+
+        let decoded = match decode(&encode(&data)) {
+            Ok(decoded) => decoded,
+            Err(error) => panic!("{}", error.message()),
+        };
+        assert!(decoded.settings.bindings.power == Modifier::Ctrl);
+        assert_eq!(decoded.settings.calibration_offset, 12.5);
+        assert_eq!(decoded.settings.volume, 0.3);
+        assert!(decoded.settings.depth_cues == data.settings.depth_cues);
+        assert_eq!(decoded.career_unlocked, 2);
+        assert_eq!(decoded.high_scores, vec![31.5, 20.]);
+        assert!(decoded.stats == data.stats);
+    }
+
+    #[test]
+    fn missing_and_invalid_fields_fall_back_to_defaults() {
+        let text = "version=1\nsettings.volume=loud\nsettings.calibration_offset=NaN\n\
+                    settings.bindings.bunt=meta\nstats.hits=3\nunknown=1\n";
+        let data = match decode(text) {
+            Ok(data) => data,
+            Err(error) => panic!("{}", error.message()),
+        };
+        let default = SaveData::default();
+        assert_eq!(data.settings.volume, default.settings.volume);
+        assert_eq!(data.settings.calibration_offset, 0.);
+        assert!(data.settings.bindings.bunt == default.settings.bindings.bunt);
+        assert_eq!(data.career_unlocked, default.career_unlocked);
+        assert_eq!(data.stats.hits, 3);
+    }
+
+    #[test]
+    fn unreadable_versions_are_errors() {
+        assert!(matches!(
+            decode("settings.volume=1\n"),
+            Err(DecodeError::MissingVersion)
+        ));
+        assert!(matches!(
+            decode(&format!("version={}\n", CURRENT_VERSION + 1)),
+            Err(DecodeError::UnknownVersion(_))
+        ));
+    }
+
+    #[test]
+    fn migrations_run_in_order_from_the_version_of_the_data() {
+        fn rename(fields: &mut Fields) {
+            if let Some(value) = fields.remove("old") {
+                fields.insert("new".to_string(), value);
+            }
+        }
+        fn double(fields: &mut Fields) {
+            let value = value(fields, "new", 0) * 2;
+            fields.insert("new".to_string(), value.to_string());
+        }
+        let migrations: [fn(&mut Fields); 2] = [rename, double];
+
+        let mut fields = Fields::new();
+        fields.insert("old".to_string(), "21".to_string());
+        migrate(&mut fields, 1, &migrations);
+        assert_eq!(fields.get("new").map(String::as_str), Some("42"));
+
+        // data of version 2 has been renamed already
+        let mut fields = Fields::new();
+        fields.insert("new".to_string(), "5".to_string());
+        migrate(&mut fields, 2, &migrations);
+        assert_eq!(fields.get("new").map(String::as_str), Some("10"));
+
+        let mut fields = Fields::new();
+        fields.insert("new".to_string(), "5".to_string());
+        migrate(&mut fields, 3, &migrations);
+        assert_eq!(fields.get("new").map(String::as_str), Some("5"));
+    }
+}
@@ -0,0 +1,67 @@
+use crate::log;
+
+use std::collections::HashMap;
+
+/// Key-value store that save data is written to.
+pub trait Storage {
+    fn get(&self, key: &str) -> Option<String>;
+    fn set(&mut self, key: &str, value: &str);
+    fn remove(&mut self, key: &str);
+}
+
+/// `window.localStorage`. Failures, e.g. on exceeding the quota, are logged and ignored.
+pub struct LocalStorage {
+    storage: web_sys::Storage,
+}
+
+/// Storage living only as long as the value, for environments without `localStorage`.
+pub struct MemoryStorage {
+    items: HashMap<String, String>,
+}
+
+impl LocalStorage {
+    pub fn new(window: &web_sys::Window) -> Option<Self> {
+        let storage = window.local_storage().ok()??;
+        Some(Self { storage })
+    }
+}
+
+impl Storage for LocalStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.storage.get_item(key).ok()?
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        if self.storage.set_item(key, value).is_err() {
+            log::log(&format!("failed to write {} to localStorage", key));
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if self.storage.remove_item(key).is_err() {
+            log::log(&format!("failed to remove {} from localStorage", key));
+        }
+    }
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self {
+            items: HashMap::new(),
+        }
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.items.get(key).cloned()
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        self.items.insert(key.to_string(), value.to_string());
+    }
+
+    fn remove(&mut self, key: &str) {
+        self.items.remove(key);
+    }
+}
@@ -0,0 +1,71 @@
+use super::{show_feedback, SceneManager, SceneType};
+use crate::camera::{Anchor, UiCameraController};
+use crate::input::InputState;
+use crate::shader::{BitmapFont, SharedContext};
+use crate::shaders::background_shader::BackgroundShader;
+use crate::shaders::text_shader::TextShader;
+use crate::text::{layout_text, Align, TextStyle};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+
+pub struct HandoffSceneContext {
+    pub scene_manager: Rc<RefCell<SceneManager>>,
+    pub background_shader: Rc<RefCell<BackgroundShader>>,
+    pub text_shader: Rc<RefCell<TextShader>>,
+    pub font: Rc<BitmapFont>,
+    pub ui_camera: Rc<RefCell<UiCameraController>>,
+    pub input: Rc<RefCell<InputState>>,
+    pub shared: Rc<RefCell<SharedContext>>,
+}
+
+/// Waits between turns for the next player to take the mouse and click.
+pub struct HandoffScene {
+    context: HandoffSceneContext,
+    message: String,
+}
+
+impl HandoffScene {
+    pub fn new(context: HandoffSceneContext) -> Self {
+        Self {
+            context,
+            message: String::new(),
+        }
+    }
+
+    pub fn render(&mut self, time: f32) -> Result<(), JsValue> {
+        let shared = self.context.shared.borrow();
+        let input = self.context.input.borrow();
+        let mut scene_manager = self.context.scene_manager.borrow_mut();
+        let mut background_shader = self.context.background_shader.borrow_mut();
+        let mut text_shader = self.context.text_shader.borrow_mut();
+        let ui_camera = self.context.ui_camera.borrow();
+
+        if let Some(message) = scene_manager.handoff_message.take() {
+            self.message = format!("{}\nClick when ready", message);
+            show_feedback(&shared.doc, "");
+        }
+
+        background_shader.clear();
+        background_shader.draw(time)?;
+
+        unsafe {
+            shared.uniform_buffer_data("ui_camera", &ui_camera.camera)?;
+        }
+        let [width, _] = ui_camera.size();
+        text_shader.clear();
+        text_shader.instances_mut().extend(layout_text(
+            &self.context.font,
+            &self.message,
+            ui_camera.anchor(Anchor::Center, [0., -60.]),
+            &TextStyle::new(Align::Center, [1., 1., 1., 1.], 1.).with_max_width(width * 0.8),
+        ));
+        text_shader.draw(time)?;
+
+        if input.pressed.is_some() {
+            scene_manager.set_scene(SceneType::Batting);
+        }
+        Ok(())
+    }
+}
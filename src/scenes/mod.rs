@@ -1,19 +1,38 @@
+mod handoff;
 mod sample;
 mod test;
 
+pub use handoff::{HandoffScene, HandoffSceneContext};
 pub use sample::{SampleScene, SampleSceneContext};
 pub use test::{TestScene, TestSceneContext};
 
+use web_sys::Document;
+
+#[derive(Clone, Copy, PartialEq)]
 pub enum SceneType {
     Batting,
-    Test,
+    Handoff,
 }
 
 pub struct SceneManager {
     pub type_: SceneType,
+    /// Message shown by the handoff scene.
+    pub handoff_message: Option<String>,
 }
 impl SceneManager {
     pub fn set_scene(&mut self, type_: SceneType) {
         self.type_ = type_;
     }
+
+    /// Pauses the game until the next player is ready.
+    pub fn hand_off(&mut self, message: String) {
+        self.handoff_message = Some(message);
+        self.set_scene(SceneType::Handoff);
+    }
+}
+
+fn show_feedback(doc: &Document, text: &str) {
+    if let Some(element) = doc.get_element_by_id("app") {
+        element.set_inner_html(text);
+    }
 }
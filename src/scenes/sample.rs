@@ -1,51 +1,148 @@
-use crate::camera::CameraController;
+use crate::camera::{
+    Anchor, CameraController, CameraDirector, CameraEffects, Rig, RigTarget, UiCameraController,
+};
 use crate::entities::sample_batter::SampleEntity;
 use crate::entities::thrown_ball::ThrownBall;
-use crate::entities::{get_current_instance_value, Renderable};
-use crate::game_state::{Batting, BattingState, GameStateBatting, GameStatePitching, Pitching};
-use crate::input::InputState;
-use crate::log;
-use crate::scenes::SceneManager;
-use crate::shader::SharedContext;
+use crate::entities::{get_current_instances, Renderable};
+use crate::game_state::{
+    Batting, BattingState, Difficulty, GameStateBatting, GameStateHitBall, GameStatePitcherAi,
+    GameStatePitching, GameStateUmpire, HitBall, HitBallState, HitResult, PitchCall, PitchOutcome,
+    PitcherAi, Pitching, SwingKind, Umpire,
+};
+use crate::hud::Hud;
+use crate::input::{InputState, Modifiers, SwingBindings};
+use crate::modes::GameMode;
+use crate::save::SaveManager;
+use crate::scenes::{show_feedback, SceneManager};
+use crate::shader::{BitmapFont, SharedContext};
 use crate::shaders::background_shader::{Background, BackgroundShader};
 use crate::shaders::entity_shader::EntityShader;
+use crate::shaders::line_shader::{polyline, LineShader};
+use crate::shaders::shadow_shader::{ball_shadow, ShadowShader};
+use crate::shaders::text_shader::TextShader;
+use crate::shaders::ui_shader::{Sprite, UiShader};
+use crate::stats::{ContactRecord, PitchRecord, SessionStats, StatTotals};
+use crate::text::{layout_text, Align, TextStyle};
 
 use std::cell::RefCell;
-use std::f32::consts::PI;
+use std::collections::VecDeque;
 use std::rc::Rc;
 use wasm_bindgen::JsValue;
-use webgl_matrix::{Mat4, Matrix, MulVectorMatrix, Vector};
+use webgl_matrix::{Mat4, Vec3, Vector};
+
+const BATTER_STANCE: Vec3 = [0., -0.8, 0.8];
+/// Where the bat's coordinate is drawn on the screen for the overlay of contact points.
+const CONTACT_OVERLAY_ORIGIN: (Anchor, [f32; 2]) = (Anchor::BottomRight, [-100., -100.]);
+/// Pixels per unit of the bat's coordinate.
+const CONTACT_OVERLAY_SCALE: f32 = 400.;
+/// Milliseconds the grade of a contact stays on the screen, fading out in the latter half.
+const CONTACT_FEEDBACK_DURATION: f32 = 1200.;
+/// Number of past ball positions drawn as the trail.
+const BALL_TRAIL_LENGTH: usize = 6;
+/// Distance from the camera where the ball is drawn at its usual size with
+/// `DepthCues::scale_with_distance`.
+const BALL_SCALING_DISTANCE: f32 = 4.;
+/// Number of points sampled along the paths of the pitch and the batted ball.
+const PATH_SAMPLES: usize = 48;
+/// Key to cycle through `Rig::SELECTABLE`.
+const CAMERA_KEY: &str = "v";
+/// Key to toggle `DepthCues::scale_with_distance`.
+const BALL_SCALING_KEY: &str = "b";
 
 pub struct SampleSceneContext {
     pub scene_manager: Rc<RefCell<SceneManager>>,
     pub entity_shader: Rc<RefCell<EntityShader>>,
     pub background_shader: Rc<RefCell<BackgroundShader>>,
+    pub line_shader: Rc<RefCell<LineShader>>,
+    pub shadow_shader: Rc<RefCell<ShadowShader>>,
+    pub ui_shader: Rc<RefCell<UiShader>>,
+    pub text_shader: Rc<RefCell<TextShader>>,
+    pub font: Rc<BitmapFont>,
     pub camera: Rc<RefCell<CameraController>>,
+    pub ui_camera: Rc<RefCell<UiCameraController>>,
     pub input: Rc<RefCell<InputState>>,
     pub shared: Rc<RefCell<SharedContext>>,
 }
 
 pub struct SampleScene<G>
 where
-    G: GameStateBatting + GameStatePitching,
+    G: GameStateBatting
+        + GameStatePitching
+        + GameStateHitBall
+        + GameStateUmpire
+        + GameStatePitcherAi,
 {
     context: SampleSceneContext,
     game_state: G,
+    mode: Box<dyn GameMode>,
+    /// Difficulty last applied to the game state.
+    difficulty: Option<Difficulty>,
+    save: SaveManager,
+    /// Stats of the past sessions.
+    saved_stats: StatTotals,
+    stats: SessionStats,
+    /// Grade of the last contact and when it was made.
+    contact_feedback: Option<(&'static str, f32)>,
+    /// Trauma of the last contact, put on the camera once the bat is drawn meeting the ball.
+    pending_impact: Option<f32>,
+    /// Whether the batter has been drawn meeting the ball in the current swing.
+    is_bat_through: bool,
     batter: SampleEntity,
     background: Background,
     ball: ThrownBall,
-    vp_inv: Mat4,
+    ball_trail: VecDeque<Vec3>,
+    trail_ball: ThrownBall,
+    landing_marker: ThrownBall,
+    contact_cursor: ThrownBall,
+    contact_overlay: ThrownBall,
+    hud: Hud,
+    is_pitch_in_flight: bool,
+    is_ball_in_play: bool,
+    /// Whether the batted ball belongs to the last pitch.
+    has_batted_ball: bool,
+    director: CameraDirector,
+    effects: CameraEffects,
+    /// Rig chosen by the player, which the camera returns to for every pitch.
+    base_rig: Rig,
+    /// What the camera frames, kept from the last frame.
+    camera_target: RigTarget,
 }
 
 impl<G> SampleScene<G>
 where
-    G: GameStateBatting + GameStatePitching,
+    G: GameStateBatting
+        + GameStatePitching
+        + GameStateHitBall
+        + GameStateUmpire
+        + GameStatePitcherAi,
 {
-    pub fn new(context: SampleSceneContext, game_state: G) -> Self {
-        Self {
+    pub fn new(
+        context: SampleSceneContext,
+        game_state: G,
+        mut mode: Box<dyn GameMode>,
+        save: SaveManager,
+    ) -> Result<Self, JsValue> {
+        let atlas = context
+            .entity_shader
+            .borrow()
+            .controller
+            .atlas("entities0.png")
+            .ok_or("entity atlas is not loaded")?;
+        mode.load(save.data());
+        let mut hud = Hud::new(&atlas, mode.keeps_count())?;
+        hud.on_scoreboard(mode.scoreboard());
+        Ok(Self {
             context,
             game_state,
-            batter: SampleEntity::new(),
+            mode,
+            difficulty: None,
+            saved_stats: save.data().stats,
+            save,
+            stats: SessionStats::new(),
+            contact_feedback: None,
+            pending_impact: None,
+            is_bat_through: false,
+            batter: SampleEntity::new(&atlas)?,
             background: Background {
                 model: [
                     9., 0., 0., 0., //
@@ -54,9 +151,25 @@ where
                     0., -1., -3., 1., //
                 ],
             },
-            ball: ThrownBall::new(),
-            vp_inv: Mat4::zeros(),
-        }
+            ball: ThrownBall::new(&atlas)?,
+            ball_trail: VecDeque::with_capacity(BALL_TRAIL_LENGTH),
+            trail_ball: ThrownBall::new(&atlas)?,
+            landing_marker: ThrownBall::new(&atlas)?,
+            contact_cursor: ThrownBall::new(&atlas)?,
+            contact_overlay: ThrownBall::new(&atlas)?,
+            hud,
+            is_pitch_in_flight: false,
+            is_ball_in_play: false,
+            has_batted_ball: false,
+            director: CameraDirector::new(Rig::Batter, 600.),
+            effects: CameraEffects::default(0),
+            base_rig: Rig::Batter,
+            camera_target: RigTarget {
+                batter: BATTER_STANCE,
+                pitcher: [0., 0., 0.],
+                ball: [0., 0., 0.],
+            },
+        })
     }
 
     pub fn render(&mut self, time: f32) -> Result<(), JsValue> {
@@ -65,14 +178,28 @@ where
         let mut camera = self.context.camera.borrow_mut();
         let mut entity_shader = self.context.entity_shader.borrow_mut();
         let mut background_shader = self.context.background_shader.borrow_mut();
+        let mut line_shader = self.context.line_shader.borrow_mut();
+        let mut shadow_shader = self.context.shadow_shader.borrow_mut();
 
         unsafe {
             shared.uniform_buffer_data("background", &self.background)?;
         }
 
-        let theta = PI / 10.;
-        camera.view.position = [0., 0., 3.];
-        camera.view.direction = [0., -theta.sin(), -theta.cos()];
+        for key in &input.keys {
+            if key.to_lowercase() == CAMERA_KEY {
+                let rigs = Rig::SELECTABLE;
+                let index = rigs.iter().position(|rig| *rig == self.base_rig);
+                self.base_rig = rigs[index.map_or(0, |index| (index + 1) % rigs.len())];
+                self.director.cut(time, self.base_rig);
+            } else if key.to_lowercase() == BALL_SCALING_KEY {
+                let depth_cues = &mut self.save.data_mut().settings.depth_cues;
+                depth_cues.scale_with_distance = !depth_cues.scale_with_distance;
+                self.save.save();
+            }
+        }
+        self.director.update(time, &self.camera_target, &mut camera);
+        self.effects.update(time);
+        self.effects.apply(&mut camera);
         camera.refresh();
         unsafe {
             shared.uniform_buffer_data("camera", &camera.camera)?;
@@ -83,53 +210,182 @@ where
 
         entity_shader.clear();
 
-        if let Some(click) = &input.clicked {
-            self.batter.start(click.timestamp);
+        for key in &input.keys {
+            self.mode.on_key(key);
         }
 
-        self.vp_inv = {
-            let mut mat = camera.camera.vp_matrix.clone();
-            mat.inverse();
-            mat
-        };
-
-        let r = [input.curr_coord.0, input.curr_coord.1, -1.0, 1.0];
-
-        let r = r.mul_matrix(&self.vp_inv);
-        let r = r.scale(1. / r[3]);
-        let r = [r[0], r[1], r[2]];
-        let r = r.sub(&camera.view.position);
-        let r = r.scale(1. / r.mag());
-        let r = r.scale((-0.8 - camera.view.position[1]) / r[1]);
-        let r = r.add(&camera.view.position);
-        // let r = r.add(&[-0.05, 0.005, 0.]);
+        let difficulty = self.mode.difficulty();
+        if self.difficulty != Some(difficulty) {
+            self.game_state.batting_mut().set_difficulty(difficulty);
+            self.game_state.pitching_mut().set_difficulty(difficulty);
+            self.game_state.pitcher_ai_mut().set_difficulty(difficulty);
+            self.difficulty = Some(difficulty);
+        }
 
         let mut pitching = self.game_state.pitching_mut();
-        if time % 800. < 10. {
-            pitching.pitch(time);
+        let mut umpire = self.game_state.umpire_mut();
+        let mut pitcher_ai = self.game_state.pitcher_ai_mut();
+        if !self.mode.is_over()
+            && !self.is_ball_in_play
+            && !pitching.is_pitching()
+            && time % 800. < 10.
+        {
+            let pitch = match self.mode.next_pitch() {
+                Some(pitch) => pitch,
+                None => pitcher_ai.choose(umpire.count()),
+            };
+            self.hud.on_pitch(&pitch, pitching.speed(&pitch));
+            pitching.pitch(time, pitch);
+            self.has_batted_ball = false;
+            self.director.cut(time, self.base_rig);
         }
         let pitching_state = pitching.update(time);
         let ball = pitching_state.ball_position;
 
+        umpire.track_ball(ball);
+        let is_pitch_passed = self.is_pitch_in_flight && ball.is_none() && !self.is_ball_in_play;
+        self.is_pitch_in_flight = ball.is_some();
+
         let mut batting = self.game_state.batting_mut();
-        batting.set_batter_position(r);
-        if let Some(click) = &input.clicked {
-            batting.swing(click.timestamp);
+        let (plane_point, plane_normal) = batting.contact_plane();
+        if let Some(cursor) = camera.pick_plane(input.curr_coord, &plane_point, &plane_normal) {
+            batting.set_contact_cursor(cursor);
+        }
+        batting.set_batter_position(BATTER_STANCE);
+        let settings = &self.save.data().settings;
+        if let Some(press) = &input.pressed {
+            batting.swing(
+                press.timestamp - settings.calibration_offset,
+                swing_kind(&settings.bindings, &press.modifiers),
+            );
+        }
+        if let Some(release) = &input.released {
+            batting.release(release.timestamp - settings.calibration_offset);
         }
         let batting_state = batting.update(time, ball);
+        let cursor = batting.contact_cursor();
 
+        let mut hit_ball = self.game_state.hit_ball_mut();
         let (batter, swing_degree) = match batting_state {
-            BattingState::Idle { batter } => (batter, 0.),
+            BattingState::Idle { batter } => (batter, None),
             BattingState::Swinging {
                 batter,
+                swing_kind,
                 swing_degree,
-            } => (batter, swing_degree),
-            BattingState::Hit(_) => {
-                log::log("aa");
-                return Ok(());
+            } => (batter, Some(pose_degree(swing_kind, swing_degree))),
+            BattingState::Hit {
+                batter,
+                swing_kind,
+                swing_degree,
+                info,
+            } => {
+                self.contact_feedback = Some((info.quality.grade.label(), time));
+                self.mode.on_contact(&info);
+                self.pending_impact = Some(0.2 + 0.4 * info.quality.grade.power());
+                hit_ball.hit(time, info);
+                if let Some(HitResult::HomeRun) =
+                    hit_ball.trajectory().map(|trajectory| &trajectory.result)
+                {
+                    self.effects.punch_fov(1.3, 900.);
+                }
+                pitching.end();
+                self.is_ball_in_play = true;
+                self.has_batted_ball = true;
+                (batter, Some(pose_degree(swing_kind, swing_degree)))
+            }
+        };
+        let mut outcome: Option<PitchOutcome> = None;
+        let (ball, landing) = match hit_ball.update(time) {
+            HitBallState::Idle {} => (ball, None),
+            HitBallState::Frying { position } => {
+                self.director.cut(time, Rig::FollowBall);
+                (
+                    Some(position),
+                    hit_ball.trajectory().map(|trajectory| trajectory.landing),
+                )
+            }
+            HitBallState::Result {
+                position,
+                result,
+                judged_at,
+            } => {
+                if judged_at == time {
+                    // look down on where the ball ended up until the next pitch
+                    if self.director.rig() == Rig::FollowBall {
+                        self.director.cut(time, Rig::Overhead);
+                    }
+                    let attempt = batting.take_attempt();
+                    outcome = Some(umpire.call(attempt, Some(result)));
+                    self.is_ball_in_play = false;
+                }
+                (Some(position), None)
             }
         };
+        if is_pitch_passed {
+            let attempt = batting.take_attempt();
+            outcome = Some(umpire.call(attempt, None));
+        }
+        if let Some(outcome) = &outcome {
+            pitcher_ai.observe(outcome);
+            let trajectory = match outcome.call {
+                PitchCall::InPlay(_) => hit_ball.trajectory(),
+                _ => None,
+            };
+            self.mode.on_outcome(outcome, trajectory);
+            self.hud.on_outcome(outcome, trajectory);
+            self.hud.on_scoreboard(self.mode.scoreboard());
+
+            let contact = hit_ball
+                .trajectory()
+                .filter(|_| self.has_batted_ball)
+                .map(ContactRecord::new);
+            self.stats.record(PitchRecord {
+                call: outcome.call.clone(),
+                at_bat: outcome.at_bat.clone(),
+                contact,
+            });
+
+            let data = self.save.data_mut();
+            self.mode.store(data);
+            data.stats = self.saved_stats.add(&self.stats.totals());
+            self.save.save();
+
+            if let Some(message) = self.mode.take_handoff() {
+                self.context.scene_manager.borrow_mut().hand_off(message);
+            }
+        }
+        if let Some(PitchOutcome { call, .. }) = &outcome {
+            let stats = format!(
+                "{}<br>{}",
+                self.stats.summary(),
+                self.stats.spray_chart(&self.background.model)
+            );
+            // the count is on the HUD
+            show_feedback(
+                &shared.doc,
+                &format!("{}<br>{}<br>{}", call.label(), self.mode.status(), stats),
+            );
+        }
         // log::log_f32(swing_degree);
+        let batter_events = self.batter.pose(time, swing_degree);
+        if swing_degree.is_none() {
+            self.is_bat_through = false;
+            self.pending_impact = None;
+        }
+        if batter_events.contains(&"bat_contact") {
+            self.is_bat_through = true;
+        }
+        // the shake lands on the frame the bat is drawn meeting the ball
+        if self.is_bat_through {
+            if let Some(trauma) = self.pending_impact.take() {
+                self.effects.add_trauma(trauma);
+            }
+        }
+        self.camera_target.batter = batter;
+        self.camera_target.pitcher = pitching_state.pitcher_position;
+        if let Some(position) = ball {
+            self.camera_target.ball = position;
+        }
 
         self.batter.set_model([
             0.8, 0., 0., 0., //
@@ -137,21 +393,176 @@ where
             0., 0., 0.8, 0., //
             batter[0], batter[1], batter[2], 1., //
         ]);
-        if let Some([x, y, z]) = ball {
-            self.ball.set_model([
-                0.8, 0., 0., 0., //
-                0., 0.8, 0., 0., //
-                0., 0., 0.8, 0., //
+        let depth_cues = self.save.data().settings.depth_cues;
+        if let Some(position) = ball {
+            let scale = if depth_cues.scale_with_distance {
+                let distance = position.sub(&camera.view.position).mag();
+                0.8 * (BALL_SCALING_DISTANCE / distance).sqrt().clamp(0.5, 2.)
+            } else {
+                0.8
+            };
+            self.ball.set_model(ball_model(position, scale));
+        }
+        self.contact_cursor.set_model([
+            0.3, 0., 0., 0., //
+            0., 0.3, 0., 0., //
+            0., 0., 0.3, 0., //
+            cursor[0], cursor[1], cursor[2], 1., //
+        ]);
+        if let Some([x, y, z]) = landing {
+            self.landing_marker.set_model([
+                0.4, 0., 0., 0., //
+                0., 0.4, 0., 0., //
+                0., 0., 0.4, 0., //
                 x, y, z, 1., //
             ]);
         }
+        shadow_shader.clear();
         {
+            let mut shadows = shadow_shader.instances_mut();
             let mut instances = entity_shader.instances_mut();
-            instances.push(get_current_instance_value(&self.batter, swing_degree));
-            instances.push(get_current_instance_value(&self.ball, 0.));
+            instances.extend(get_current_instances(&self.batter));
+            if let Some(position) = ball {
+                if depth_cues.shadow {
+                    // the field lies at the height of its origin
+                    shadows.push(ball_shadow(position, self.background.model[13]));
+                }
+                if depth_cues.trail {
+                    // older positions are fainter and smaller
+                    let len = self.ball_trail.len() as f32;
+                    for (i, &past) in self.ball_trail.iter().enumerate() {
+                        let weight = (i + 1) as f32 / (len + 1.);
+                        self.trail_ball
+                            .set_model(ball_model(past, 0.8 * (0.5 + 0.5 * weight)));
+                        instances.extend(get_current_instances(&self.trail_ball).into_iter().map(
+                            |mut instance| {
+                                instance.opacity *= 0.5 * weight;
+                                instance
+                            },
+                        ));
+                    }
+                }
+                if self.ball_trail.len() >= BALL_TRAIL_LENGTH {
+                    self.ball_trail.pop_front();
+                }
+                self.ball_trail.push_back(position);
+            } else {
+                self.ball_trail.clear();
+            }
+            instances.extend(get_current_instances(&self.ball));
+            instances.extend(get_current_instances(&self.contact_cursor));
+            if landing.is_some() {
+                instances.extend(get_current_instances(&self.landing_marker));
+            }
         }
+
+        line_shader.clear();
+        if self.mode.shows_paths() {
+            let mut lines = line_shader.instances_mut();
+            // the path would give the pitch away while it is in flight
+            if !pitching.is_pitching() {
+                lines.extend(polyline(
+                    &pitching.path(PATH_SAMPLES),
+                    [1., 1., 1., 0.6],
+                    0.006,
+                    false,
+                ));
+            }
+            if self.has_batted_ball {
+                lines.extend(polyline(
+                    &hit_ball.path(PATH_SAMPLES),
+                    [1., 0.85, 0.2, 0.8],
+                    0.006,
+                    true,
+                ));
+            }
+        }
+        // under the sprites standing on the ground
+        shadow_shader.draw(time)?;
+        entity_shader.sort_instances(&camera.camera.view_matrix);
         entity_shader.draw(time)?;
+        // translucent, so drawn over the sorted entities
+        line_shader.draw(time)?;
+
+        // UI pass
+        let ui_camera = self.context.ui_camera.borrow();
+        let mut ui_shader = self.context.ui_shader.borrow_mut();
+        let mut text_shader = self.context.text_shader.borrow_mut();
+        unsafe {
+            shared.uniform_buffer_data("ui_camera", &ui_camera.camera)?;
+        }
+        ui_shader.clear();
+        text_shader.clear();
+        {
+            let mut instances = ui_shader.instances_mut();
+            let (anchor, offset) = CONTACT_OVERLAY_ORIGIN;
+            let [ox, oy] = ui_camera.anchor(anchor, offset);
+            for [x, y] in self.mode.contact_points() {
+                // the bat's Y axis points up while the screen's points down
+                self.contact_overlay.set_model(ball_model(
+                    [
+                        ox + x * CONTACT_OVERLAY_SCALE,
+                        oy - y * CONTACT_OVERLAY_SCALE,
+                        0.,
+                    ],
+                    80.,
+                ));
+                instances.extend(
+                    get_current_instances(&self.contact_overlay)
+                        .into_iter()
+                        .map(Sprite::from),
+                );
+            }
+            let mut glyphs = text_shader.instances_mut();
+            self.hud
+                .draw(&ui_camera, &self.context.font, &mut instances, &mut glyphs);
+        }
+        ui_shader.draw(time)?;
+        if let Some((label, shown_at)) = self.contact_feedback {
+            let t = (time - shown_at) / CONTACT_FEEDBACK_DURATION;
+            if t < 1. {
+                let alpha = (2. - 2. * t).min(1.);
+                text_shader.instances_mut().extend(layout_text(
+                    &self.context.font,
+                    label,
+                    ui_camera.anchor(Anchor::Center, [0., -120.]),
+                    &TextStyle::new(Align::Center, [1., 0.9, 0.3, alpha], 2.),
+                ));
+            } else {
+                self.contact_feedback = None;
+            }
+        }
+        text_shader.draw(time)?;
 
         Ok(())
     }
 }
+
+fn ball_model([x, y, z]: Vec3, scale: f32) -> Mat4 {
+    [
+        scale, 0., 0., 0., //
+        0., scale, 0., 0., //
+        0., 0., scale, 0., //
+        x, y, z, 1., //
+    ]
+}
+
+fn swing_kind(bindings: &SwingBindings, modifiers: &Modifiers) -> SwingKind {
+    if modifiers.is_held(bindings.bunt) {
+        SwingKind::Bunt
+    } else if modifiers.is_held(bindings.power) {
+        SwingKind::Power
+    } else if modifiers.is_held(bindings.contact) {
+        SwingKind::Contact
+    } else {
+        SwingKind::Normal
+    }
+}
+
+/// Bunt holds the batter in the squared-up frame instead of following the swing.
+fn pose_degree(kind: SwingKind, swing_degree: f32) -> f32 {
+    match kind {
+        SwingKind::Bunt => 0.3,
+        _ => swing_degree,
+    }
+}
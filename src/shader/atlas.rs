@@ -0,0 +1,105 @@
+use js_sys::{Object, Reflect, JSON};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::Response;
+
+/// A sprite in the atlas, converted into texture coordinates.
+#[derive(Clone)]
+pub struct AtlasFrame {
+    pub uv_offset: [f32; 2],
+    pub uv_scale: [f32; 2],
+    /// Offset of the quad that puts the pivot on the origin, in units of the quad's half size.
+    pub pivot_offset: [f32; 2],
+}
+
+/// Sprite sheet metadata in TexturePacker's JSON (hash) format:
+///
+/// ```json
+/// {
+///   "frames": {
+///     "ball": { "frame": { "x": 0, "y": 0, "w": 102, "h": 102 }, "pivot": { "x": 0.5, "y": 0.5 } }
+///   },
+///   "meta": { "size": { "w": 2048, "h": 2048 } }
+/// }
+/// ```
+///
+/// Rectangles are in pixels from the top-left corner of the image and pivots are normalized in
+/// the rectangle. Rotated frames are not supported.
+pub struct Atlas {
+    frames: HashMap<String, AtlasFrame>,
+}
+
+impl Atlas {
+    pub fn frame(&self, name: &str) -> Result<AtlasFrame, String> {
+        self.frames
+            .get(name)
+            .cloned()
+            .ok_or(format!("unknown atlas frame '{}'", name))
+    }
+
+    fn from_json(json: &JsValue) -> Result<Self, JsValue> {
+        let size = get(&get(json, "meta")?, "size")?;
+        let (width, height) = (number(&size, "w")?, number(&size, "h")?);
+
+        let mut frames = HashMap::new();
+        let entries = get(json, "frames")?.dyn_into::<Object>()?;
+        for entry in Object::entries(&entries).iter() {
+            let name = Reflect::get_u32(&entry, 0)?
+                .as_string()
+                .ok_or("invalid frame name")?;
+            let value = Reflect::get_u32(&entry, 1)?;
+            if get(&value, "rotated")?.as_bool() == Some(true) {
+                return Err(format!("rotated frame '{}' is not supported", name).into());
+            }
+            let rect = get(&value, "frame")?;
+            let pivot = get(&value, "pivot")?;
+            let (pivot_x, pivot_y) = if pivot.is_undefined() {
+                (0.5, 0.5)
+            } else {
+                (number(&pivot, "x")?, number(&pivot, "y")?)
+            };
+            frames.insert(
+                name,
+                AtlasFrame {
+                    uv_offset: [number(&rect, "x")? / width, number(&rect, "y")? / height],
+                    uv_scale: [number(&rect, "w")? / width, number(&rect, "h")? / height],
+                    // the quad spans [-1, 1] and its y axis points up
+                    pivot_offset: [1. - 2. * pivot_x, 2. * pivot_y - 1.],
+                },
+            );
+        }
+        Ok(Self { frames })
+    }
+}
+
+pub(super) async fn fetch_text(src: &str) -> Result<String, JsValue> {
+    let window = web_sys::window().ok_or("window is unavailable")?;
+    let response = JsFuture::from(window.fetch_with_str(src))
+        .await?
+        .dyn_into::<Response>()?;
+    if !response.ok() {
+        return Err(format!("failed to fetch {}: {}", src, response.status()).into());
+    }
+    let text = JsFuture::from(response.text()?).await?;
+    Ok(text
+        .as_string()
+        .ok_or_else(|| format!("{} is not a text", src))?)
+}
+
+pub(super) async fn load_atlas(src: &str) -> Result<Atlas, JsValue> {
+    let json = JSON::parse(&fetch_text(src).await?)?;
+    Atlas::from_json(&json)
+}
+
+fn get(target: &JsValue, key: &str) -> Result<JsValue, JsValue> {
+    Reflect::get(target, &JsValue::from_str(key))
+}
+
+fn number(target: &JsValue, key: &str) -> Result<f32, JsValue> {
+    get(target, key)?
+        .as_f64()
+        .map(|value| value as f32)
+        .ok_or_else(|| format!("atlas field '{}' is not a number", key).into())
+}
@@ -0,0 +1,189 @@
+use super::atlas::fetch_text;
+
+use std::collections::HashMap;
+use wasm_bindgen::JsValue;
+
+/// A character of the font in pixels of the page texture.
+#[derive(Clone)]
+pub struct FontGlyph {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Offset from the pen position to the top left of the glyph.
+    pub offset: [f32; 2],
+    /// How far the pen moves after the glyph.
+    pub advance: f32,
+}
+
+/// Bitmap font in the text format of AngelCode BMFont:
+///
+/// ```text
+/// common lineHeight=32 base=26 scaleW=256 scaleH=256 pages=1
+/// page id=0 file="font.png"
+/// char id=65 x=16 y=64 width=16 height=32 xoffset=0 yoffset=0 xadvance=16 page=0 chnl=15
+/// kerning first=65 second=86 amount=-1
+/// ```
+///
+/// Only fonts of a single page are supported.
+pub struct BitmapFont {
+    pub line_height: f32,
+    /// Distance from the top of a line to the baseline.
+    pub base: f32,
+    /// Size of the page texture.
+    pub scale: [f32; 2],
+    pub page: String,
+    glyphs: HashMap<char, FontGlyph>,
+    kernings: HashMap<(char, char), f32>,
+}
+
+impl BitmapFont {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut font = Self {
+            line_height: 0.,
+            base: 0.,
+            scale: [1., 1.],
+            page: String::new(),
+            glyphs: HashMap::new(),
+            kernings: HashMap::new(),
+        };
+        for line in text.lines() {
+            let mut tokens = line.split_whitespace();
+            let tag = match tokens.next() {
+                Some(tag) => tag,
+                None => continue,
+            };
+            let attributes = tokens
+                .filter_map(|token| {
+                    let mut pair = token.splitn(2, '=');
+                    Some((pair.next()?, pair.next()?.trim_matches('"')))
+                })
+                .collect::<HashMap<_, _>>();
+            let number = |key: &str| -> Result<f32, String> {
+                attributes
+                    .get(key)
+                    .and_then(|value| value.parse::<f32>().ok())
+                    .ok_or_else(|| format!("font field '{}' of '{}' is not a number", key, tag))
+            };
+            let character = |key: &str| -> Result<char, String> {
+                std::char::from_u32(number(key)? as u32)
+                    .ok_or_else(|| format!("font field '{}' is not a character", key))
+            };
+            match tag {
+                "common" => {
+                    if number("pages")? > 1. {
+                        return Err("fonts of multiple pages are not supported".to_string());
+                    }
+                    font.line_height = number("lineHeight")?;
+                    font.base = number("base")?;
+                    font.scale = [number("scaleW")?, number("scaleH")?];
+                }
+                "page" => {
+                    font.page = attributes
+                        .get("file")
+                        .ok_or("font page has no file")?
+                        .to_string();
+                }
+                "char" => {
+                    font.glyphs.insert(
+                        character("id")?,
+                        FontGlyph {
+                            x: number("x")?,
+                            y: number("y")?,
+                            width: number("width")?,
+                            height: number("height")?,
+                            offset: [number("xoffset")?, number("yoffset")?],
+                            advance: number("xadvance")?,
+                        },
+                    );
+                }
+                "kerning" => {
+                    font.kernings.insert(
+                        (character("first")?, character("second")?),
+                        number("amount")?,
+                    );
+                }
+                _ => {}
+            }
+        }
+        if font.line_height <= 0. {
+            return Err("font has no common line".to_string());
+        }
+        Ok(font)
+    }
+
+    pub async fn load(src: &str) -> Result<Self, JsValue> {
+        Ok(Self::parse(&fetch_text(src).await?)?)
+    }
+
+    /// Glyph of `c`, or of `?` if the font doesn't have it.
+    pub fn glyph(&self, c: char) -> Option<&FontGlyph> {
+        self.glyphs.get(&c).or_else(|| self.glyphs.get(&'?'))
+    }
+
+    pub fn kerning(&self, first: char, second: char) -> f32 {
+        self.kernings.get(&(first, second)).copied().unwrap_or(0.)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FONT: &str = r#"info face="Test" size=16
+common lineHeight=20 base=16 scaleW=128 scaleH=64 pages=1
+page id=0 file="test.png"
+
+chars count=2
+char id=65 x=0 y=0 width=10 height=16 xoffset=1 yoffset=2 xadvance=11 page=0 chnl=15
+char id=63 x=10 y=0 width=8 height=16 xoffset=0 yoffset=2 xadvance=9 page=0 chnl=15
+kernings count=1
+kerning first=65 second=63 amount=-2
+"#;
+
+    #[test]
+    fn parses_a_minimal_font() {
+        let font = BitmapFont::parse(FONT).unwrap();
+        assert_eq!(font.line_height, 20.);
+        assert_eq!(font.base, 16.);
+        assert_eq!(font.scale, [128., 64.]);
+        assert_eq!(font.page, "test.png");
+
+        let a = font.glyph('A').unwrap();
+        assert_eq!([a.x, a.y, a.width, a.height], [0., 0., 10., 16.]);
+        assert_eq!(a.offset, [1., 2.]);
+        assert_eq!(a.advance, 11.);
+        // characters missing from the font fall back to '?'
+        assert_eq!(font.glyph('Z').unwrap().x, 10.);
+    }
+
+    #[test]
+    fn kerning_applies_to_its_pair_in_order() {
+        let font = BitmapFont::parse(FONT).unwrap();
+        assert_eq!(font.kerning('A', '?'), -2.);
+        assert_eq!(font.kerning('?', 'A'), 0.);
+        assert_eq!(font.kerning('A', 'A'), 0.);
+    }
+
+    #[test]
+    fn font_without_a_common_line_is_rejected() {
+        let text = FONT.replace("common", "uncommon");
+        assert!(BitmapFont::parse(&text).is_err());
+        assert!(BitmapFont::parse("").is_err());
+    }
+
+    #[test]
+    fn malformed_lines_are_rejected() {
+        let missing = FONT.replace(" xadvance=11", "");
+        assert_eq!(
+            BitmapFont::parse(&missing).err().as_deref(),
+            Some("font field 'xadvance' of 'char' is not a number")
+        );
+        let not_number = FONT.replace("x=0 y=0", "x=left y=0");
+        assert!(BitmapFont::parse(&not_number).is_err());
+        let no_file = FONT.replace(r#" file="test.png""#, "");
+        assert!(BitmapFont::parse(&no_file).is_err());
+        let pages = FONT.replace("pages=1", "pages=2");
+        assert!(BitmapFont::parse(&pages).is_err());
+    }
+}
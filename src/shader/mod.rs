@@ -2,18 +2,25 @@
 mod macros;
 
 mod array_buffer;
+mod atlas;
 mod buffer_data;
 mod compile;
+mod font;
 mod texture;
 mod uniform_buffer;
 
+pub use atlas::{Atlas, AtlasFrame};
 pub use buffer_data::ConvertArrayView;
+pub use font::BitmapFont;
+pub use texture::load_texture;
 use uniform_buffer::UniformBuffers;
 
 use std::cell::{Ref, RefCell, RefMut};
+use std::cmp::Ordering;
 use std::rc::Rc;
 use wasm_bindgen::JsValue;
 use web_sys::{Document, WebGl2RenderingContext};
+use webgl_matrix::{Mat4, MulVectorMatrix, Vec3};
 
 pub struct SharedContext {
     pub doc: Rc<Document>,
@@ -51,6 +58,14 @@ impl ShaderController {
     }
 }
 
+/// Where an instance is drawn among the others of a shader. Translucent instances are drawn after
+/// the opaque ones from back to front.
+pub struct DrawOrder {
+    pub is_translucent: bool,
+    /// Position in the world that the depth is measured at.
+    pub position: Vec3,
+}
+
 pub trait ShaderImpl<I>
 where
     I: Sized,
@@ -60,6 +75,14 @@ where
     fn init(&self, shader: &mut ShaderController) -> Result<(), JsValue>;
     fn get_static_instances(&self) -> Option<Vec<I>>;
     fn get_texture_map(&self) -> Vec<(u32, u32, &'static str)>;
+    /// Pairs of a texture and its atlas, loaded along with the texture.
+    fn get_atlas_map(&self) -> Vec<(&'static str, &'static str)> {
+        vec![]
+    }
+    /// Instances are drawn in the pushed order unless this is given.
+    fn draw_order(&self, _instance: &I) -> Option<DrawOrder> {
+        None
+    }
     fn draw(&self, ctx: &WebGl2RenderingContext, time: f32, instance_len: i32);
 }
 
@@ -109,10 +132,31 @@ where
         })))
     }
 
-    pub async fn init_textures(&mut self) -> Result<(), JsValue> {
-        self.controller.activate();
-        for (_, _, filename) in self.implementation.get_texture_map() {
-            self.controller.create_texture(filename).await?;
+    /// Loads the textures of `shader`, which is only borrowed between the loads.
+    pub async fn init_textures(shader: &Rc<RefCell<Self>>) -> Result<(), JsValue> {
+        let (doc, sources) = {
+            let shader = shader.borrow();
+            let atlas_map = shader.implementation.get_atlas_map();
+            let sources = shader
+                .implementation
+                .get_texture_map()
+                .into_iter()
+                .map(|(_, _, filename)| {
+                    let atlas = atlas_map
+                        .iter()
+                        .find(|(texture, _)| *texture == filename)
+                        .map(|(_, atlas)| *atlas);
+                    (filename, atlas)
+                })
+                .collect::<Vec<_>>();
+            let doc = shader.controller.shared.borrow().doc.clone();
+            (doc, sources)
+        };
+        for (filename, atlas) in sources {
+            let source = load_texture(&doc, filename, atlas).await?;
+            let mut shader = shader.borrow_mut();
+            shader.controller.activate();
+            shader.controller.create_texture(filename, source)?;
         }
         Ok(())
     }
@@ -131,6 +175,45 @@ where
         self.instances.borrow_mut()
     }
 
+    /// Sorts the instances by `ShaderImpl::draw_order` seen through `view_matrix`. Opaque instances
+    /// keep the pushed order.
+    pub fn sort_instances(&mut self, view_matrix: &Mat4) {
+        if self.is_static_instance {
+            return;
+        }
+        let implementation = &self.implementation;
+        let mut keyed = self
+            .instances
+            .borrow_mut()
+            .drain(..)
+            .map(|instance| {
+                let key = implementation.draw_order(&instance).map(|order| {
+                    let [x, y, z] = order.position;
+                    // the camera looks toward -Z in the view space, so farther is smaller
+                    let depth = if order.is_translucent {
+                        [x, y, z, 1.].mul_matrix(view_matrix)[2]
+                    } else {
+                        f32::MIN
+                    };
+                    (order.is_translucent, depth)
+                });
+                (key, instance)
+            })
+            .collect::<Vec<_>>();
+        keyed.sort_by(|(a, _), (b, _)| match (a, b) {
+            (Some(a), Some(b)) => {
+                a.0.cmp(&b.0)
+                    .then(a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+            }
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+        self.instances
+            .borrow_mut()
+            .extend(keyed.into_iter().map(|(_, instance)| instance));
+    }
+
     pub fn draw(&mut self, time: f32) -> Result<(), JsValue> {
         self.controller.activate();
         for (tex_id, tex_slot, filename) in self.implementation.get_texture_map() {
@@ -1,16 +1,21 @@
+use super::atlas::{load_atlas, Atlas};
 use super::ShaderController;
 use web_sys::WebGlProgram;
 
 use std::collections::HashMap;
+use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{HtmlImageElement, WebGl2RenderingContext, WebGlTexture, WebGlUniformLocation};
+use web_sys::{
+    Document, HtmlImageElement, WebGl2RenderingContext, WebGlTexture, WebGlUniformLocation,
+};
 
 pub struct Textures {
     uniforms: HashMap<u32, WebGlUniformLocation>,
     cache_tex: HashMap<&'static str, WebGlTexture>,
     cache_img: HashMap<&'static str, HtmlImageElement>,
+    cache_atlas: HashMap<&'static str, Rc<Atlas>>,
 }
 
 impl Textures {
@@ -19,21 +24,48 @@ impl Textures {
             uniforms: HashMap::new(),
             cache_tex: HashMap::new(),
             cache_img: HashMap::new(),
+            cache_atlas: HashMap::new(),
         }
     }
 }
 
+/// Image of a texture and its atlas, loaded before the texture is created.
+pub struct TextureSource {
+    image: HtmlImageElement,
+    atlas: Option<Atlas>,
+}
+
+/// Loads the image `src` and the atlas of it if any. Nothing is borrowed while loading, so that
+/// the shaders can be used in the meantime.
+pub async fn load_texture(
+    doc: &Document,
+    src: &str,
+    atlas: Option<&str>,
+) -> Result<TextureSource, JsValue> {
+    let atlas = match atlas {
+        Some(atlas) => Some(load_atlas(atlas).await?),
+        None => None,
+    };
+    Ok(TextureSource {
+        image: load_image(doc, src).await?,
+        atlas,
+    })
+}
+
 impl ShaderController {
-    pub async fn create_texture<'a>(&'a mut self, src: &'static str) -> Result<(), JsValue> {
+    pub fn create_texture(
+        &mut self,
+        src: &'static str,
+        source: TextureSource,
+    ) -> Result<(), JsValue> {
+        if let Some(atlas) = source.atlas {
+            self.textures.cache_atlas.insert(src, Rc::new(atlas));
+        }
         if self.textures.cache_tex.contains_key(src) {
             return Ok(());
         }
         let shared = self.shared.borrow();
-        if !self.textures.cache_img.contains_key(src) {
-            self.textures
-                .cache_img
-                .insert(src, load_image(&shared.doc, src).await?);
-        }
+        self.textures.cache_img.insert(src, source.image);
         let img = self.textures.cache_img.get(src);
         let img = img.as_ref().unwrap();
         let tex = shared
@@ -62,6 +94,11 @@ impl ShaderController {
         Ok(())
     }
 
+    /// Atlas loaded with the texture.
+    pub fn atlas(&self, src: &str) -> Option<Rc<Atlas>> {
+        self.textures.cache_atlas.get(src).cloned()
+    }
+
     pub fn bind_texture(&self, tex_slot: u32, src: &str) -> Result<(), JsValue> {
         let tex = self.textures.cache_tex.get(src).ok_or("unknown texture")?;
         let shared = self.shared.borrow();
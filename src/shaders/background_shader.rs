@@ -71,6 +71,8 @@ layout (std140) uniform background {
 
 layout (std140) uniform camera {
     mat4 vpMatrix;
+    mat4 viewMatrix;
+    float aspectRatio;
 };
 
 void main() {
@@ -90,7 +92,7 @@ out vec4 outColor;
 
 void main() {
     vec4 tex_color = texture(tex0, v_uv);
-    outColor = tex_color;
+    outColor = vec4(tex_color.rgb * tex_color.a, tex_color.a);
 }
 "#;
 
@@ -1,4 +1,4 @@
-use crate::shader::{Shader, ShaderController, ShaderImpl};
+use crate::shader::{DrawOrder, Shader, ShaderController, ShaderImpl};
 use crate::ConvertArrayView;
 
 use wasm_bindgen::JsValue;
@@ -20,8 +20,42 @@ pub struct Instance {
     pub uv_offset: [f32; 2],
     pub uv_scale: [f32; 2],
     pub pos_offset: [f32; 2],
+    pub opacity: f32,
+    /// `Billboard` as a float.
+    pub billboard: f32,
 }
 
+/// How a sprite is turned toward the camera. The sprite keeps the position and the scale of its
+/// model but ignores the rotation unless it is `None`.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Billboard {
+    None,
+    /// Faces the camera from every direction.
+    Spherical,
+    /// Stands upright and only turns around the Y axis.
+    Cylindrical,
+}
+
+impl From<Billboard> for f32 {
+    fn from(billboard: Billboard) -> Self {
+        match billboard {
+            Billboard::None => 0.,
+            Billboard::Spherical => 1.,
+            Billboard::Cylindrical => 2.,
+        }
+    }
+}
+
+/// Attributes of `Instance` in the order of its fields, as numbers of floats.
+const INSTANCE_LAYOUT: [(&str, i32); 6] = [
+    ("model", 16),
+    ("uv_offset", 2),
+    ("uv_scale", 2),
+    ("pos_offset", 2),
+    ("opacity", 1),
+    ("billboard", 1),
+];
+
 impl ConvertArrayView for [Vertex; 6] {}
 static VERTICES: [Vertex; 6] = [
     Vertex {
@@ -58,17 +92,39 @@ layout (location = 2) in mat4 model;
 layout (location = 6) in vec2 uv_offset;
 layout (location = 7) in vec2 uv_scale;
 layout (location = 8) in vec2 pos_offset;
+layout (location = 9) in float opacity;
+layout (location = 10) in float billboard;
 
 out vec2 v_uv;
+out float v_opacity;
 
 layout (std140) uniform camera {
     mat4 vpMatrix;
+    mat4 viewMatrix;
+    float aspectRatio;
 };
 
 void main() {
     v_uv = uv_scale * uv + uv_offset;
-    mat4 mvp = vpMatrix * model;
-    gl_Position = mvp * vec4((position + pos_offset) * uv_scale, 0.0, 1.0);
+    v_opacity = opacity;
+    vec2 local = (position + pos_offset) * uv_scale;
+    if (billboard < 0.5) {
+        gl_Position = vpMatrix * model * vec4(local, 0.0, 1.0);
+        return;
+    }
+
+    // axes of the camera in the world, which are the rows of the view matrix
+    vec3 right = vec3(viewMatrix[0][0], viewMatrix[1][0], viewMatrix[2][0]);
+    vec3 up = vec3(viewMatrix[0][1], viewMatrix[1][1], viewMatrix[2][1]);
+    if (billboard > 1.5) {
+        right = vec3(right.x, 0.0, right.z);
+        up = vec3(0.0, 1.0, 0.0);
+    }
+    right = normalize(right);
+    up = normalize(up);
+    vec2 scale = vec2(length(model[0].xyz), length(model[1].xyz));
+    vec3 world = model[3].xyz + right * local.x * scale.x + up * local.y * scale.y;
+    gl_Position = vpMatrix * vec4(world, 1.0);
 }
 "#;
 
@@ -77,16 +133,20 @@ precision highp float;
 uniform sampler2D tex0;
 
 in vec2 v_uv;
+in float v_opacity;
 
 out vec4 outColor;
 
+// texels more transparent than this are cut out so that they don't hide what is behind
+const float ALPHA_CUTOFF = 0.1;
+
 void main() {
     vec4 tex_color = texture(tex0, v_uv);
-    outColor = tex_color;
-    float alpha = tex_color.a;
-    float depth = gl_FragCoord.z;
-    // TODO: correct formula
-    gl_FragDepth = (alpha * depth) + (1.0 - alpha);
+    if (tex_color.a < ALPHA_CUTOFF) {
+        discard;
+    }
+    float alpha = tex_color.a * v_opacity;
+    outColor = vec4(tex_color.rgb * alpha, alpha);
 }
 "#;
 
@@ -105,16 +165,7 @@ impl ShaderImpl<Instance> for EntityShaderImpl {
         shader.compile(VERT, FRAG)?;
         shader.bind_uniform_blocks(vec!["camera"])?;
         shader.layout_buffer::<Vertex>("vertex", 0, vec![("position", 2), ("uv", 2)])?;
-        shader.layout_buffer::<Instance>(
-            "instance",
-            1,
-            vec![
-                ("model", 16),
-                ("uv_offset", 2),
-                ("uv_scale", 2),
-                ("pos_offset", 2),
-            ],
-        )?;
+        shader.layout_buffer::<Instance>("instance", 1, INSTANCE_LAYOUT.to_vec())?;
         unsafe {
             shader.buffer_data_static("vertex", &VERTICES)?;
         }
@@ -125,6 +176,18 @@ impl ShaderImpl<Instance> for EntityShaderImpl {
         vec![(0, 0, "entities0.png")]
     }
 
+    fn get_atlas_map(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("entities0.png", "entities0.atlas.json")]
+    }
+
+    fn draw_order(&self, instance: &Instance) -> Option<DrawOrder> {
+        let model = &instance.model;
+        Some(DrawOrder {
+            is_translucent: instance.opacity < 1.,
+            position: [model[12], model[13], model[14]],
+        })
+    }
+
     fn draw(&self, ctx: &WebGl2RenderingContext, _: f32, instance_len: i32) {
         ctx.draw_arrays_instanced(
             WebGl2RenderingContext::TRIANGLES,
@@ -134,3 +197,14 @@ impl ShaderImpl<Instance> for EntityShaderImpl {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instance_layout_covers_every_byte() {
+        let floats: i32 = INSTANCE_LAYOUT.iter().map(|(_, len)| len).sum();
+        assert_eq!(floats as usize * 4, std::mem::size_of::<Instance>());
+    }
+}
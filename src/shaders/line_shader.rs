@@ -0,0 +1,139 @@
+use crate::shader::{Shader, ShaderController, ShaderImpl};
+use crate::ConvertArrayView;
+
+use wasm_bindgen::JsValue;
+use web_sys::WebGl2RenderingContext;
+use webgl_matrix::Vec3;
+
+pub type LineShader = Shader<LineShaderImpl, Instance>;
+pub struct LineShaderImpl {}
+
+#[repr(C)]
+struct Vertex {
+    /// Position along the segment in `[0, 1]` and the side of the ribbon in `[-1, 1]`.
+    corner: [f32; 2],
+}
+
+/// A segment of a ribbon, which keeps the same width on the screen.
+#[repr(C)]
+pub struct Instance {
+    pub start: Vec3,
+    pub end: Vec3,
+    pub color: [f32; 4],
+    /// Width in normalized device coordinates along the height of the screen.
+    pub width: f32,
+}
+
+impl ConvertArrayView for [Vertex; 6] {}
+static VERTICES: [Vertex; 6] = [
+    Vertex { corner: [0., -1.] },
+    Vertex { corner: [1., -1.] },
+    Vertex { corner: [0., 1.] },
+    Vertex { corner: [0., 1.] },
+    Vertex { corner: [1., -1.] },
+    Vertex { corner: [1., 1.] },
+];
+
+const VERT: &str = r#"#version 300 es
+layout (location = 0) in vec2 corner;
+
+layout (location = 1) in vec3 start;
+layout (location = 2) in vec3 end;
+layout (location = 3) in vec4 color;
+layout (location = 4) in float width;
+
+out vec4 v_color;
+
+layout (std140) uniform camera {
+    mat4 vpMatrix;
+    mat4 viewMatrix;
+    float aspectRatio;
+};
+
+void main() {
+    v_color = color;
+    vec4 a = vpMatrix * vec4(start, 1.0);
+    vec4 b = vpMatrix * vec4(end, 1.0);
+    // the normal is taken on the screen, where both axes have the same scale, and brought back
+    // to normalized device coordinates
+    vec2 aspect = vec2(aspectRatio, 1.0);
+    vec2 direction = (b.xy / b.w - a.xy / a.w) * aspect;
+    vec2 normal = length(direction) > 0.0
+        ? normalize(vec2(-direction.y, direction.x)) / aspect
+        : vec2(0.0, 1.0);
+    vec4 p = mix(a, b, corner.x);
+    // offset in clip space so that the width doesn't depend on the depth
+    gl_Position = p + vec4(normal * width * 0.5 * corner.y * p.w, 0.0, 0.0);
+}
+"#;
+
+const FRAG: &str = r#"#version 300 es
+precision highp float;
+
+in vec4 v_color;
+
+out vec4 outColor;
+
+void main() {
+    outColor = vec4(v_color.rgb * v_color.a, v_color.a);
+}
+"#;
+
+/// Segments joining `points` in order. Every other segment is skipped if `dashed`.
+pub fn polyline(points: &[Vec3], color: [f32; 4], width: f32, dashed: bool) -> Vec<Instance> {
+    points
+        .windows(2)
+        .enumerate()
+        .filter(|(i, _)| !dashed || i % 2 == 0)
+        .map(|(_, pair)| Instance {
+            start: pair[0],
+            end: pair[1],
+            color,
+            width,
+        })
+        .collect()
+}
+
+impl ShaderImpl<Instance> for LineShaderImpl {
+    const INSTANCE_CAPACITY: Option<usize> = None;
+
+    fn new() -> Self {
+        Self {}
+    }
+
+    fn get_static_instances(&self) -> Option<Vec<Instance>> {
+        None
+    }
+
+    fn init(&self, shader: &mut ShaderController) -> Result<(), JsValue> {
+        shader.compile(VERT, FRAG)?;
+        shader.bind_uniform_blocks(vec!["camera"])?;
+        shader.layout_buffer::<Vertex>("vertex", 0, vec![("corner", 2)])?;
+        shader.layout_buffer::<Instance>(
+            "instance",
+            1,
+            vec![("start", 3), ("end", 3), ("color", 4), ("width", 1)],
+        )?;
+        unsafe {
+            shader.buffer_data_static("vertex", &VERTICES)?;
+        }
+        Ok(())
+    }
+
+    fn get_texture_map(&self) -> Vec<(u32, u32, &'static str)> {
+        vec![]
+    }
+
+    fn draw(&self, ctx: &WebGl2RenderingContext, _: f32, instance_len: i32) {
+        // Ribbons are translucent, so they are tested against the depth but don't hide what is
+        // drawn behind them later.
+        ctx.depth_mask(false);
+        ctx.draw_arrays_instanced(
+            WebGl2RenderingContext::TRIANGLES,
+            0,
+            VERTICES.len() as i32,
+            instance_len,
+        );
+        ctx.depth_mask(true);
+    }
+}
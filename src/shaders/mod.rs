@@ -1,3 +1,7 @@
 pub mod background_shader;
 pub mod entity_shader;
+pub mod line_shader;
+pub mod shadow_shader;
 pub mod test;
+pub mod text_shader;
+pub mod ui_shader;
@@ -0,0 +1,134 @@
+use crate::shader::{Shader, ShaderController, ShaderImpl};
+use crate::ConvertArrayView;
+
+use wasm_bindgen::JsValue;
+use web_sys::WebGl2RenderingContext;
+use webgl_matrix::{Mat4, Vec3};
+
+/// Size of the shadow of a ball on the ground.
+const SIZE: f32 = 0.8;
+/// Lifts the shadow off the ground so that it isn't hidden by the ground.
+const LIFT: f32 = 0.01;
+
+/// Draws blob shadows on the ground. The blob is a radial falloff computed in the fragment
+/// shader, so it doesn't need a texture.
+pub type ShadowShader = Shader<ShadowShaderImpl, Instance>;
+pub struct ShadowShaderImpl {}
+
+#[repr(C)]
+struct Vertex {
+    /// Position in the quad in `[-1, 1]`.
+    corner: [f32; 2],
+}
+
+#[repr(C)]
+pub struct Instance {
+    /// Places the quad, which lies in the XY plane of the model.
+    pub model: Mat4,
+    /// Opacity at the center of the blob.
+    pub opacity: f32,
+}
+
+impl ConvertArrayView for [Vertex; 6] {}
+static VERTICES: [Vertex; 6] = [
+    Vertex { corner: [-1., -1.] },
+    Vertex { corner: [1., -1.] },
+    Vertex { corner: [-1., 1.] },
+    Vertex { corner: [-1., 1.] },
+    Vertex { corner: [1., -1.] },
+    Vertex { corner: [1., 1.] },
+];
+
+const VERT: &str = r#"#version 300 es
+layout (location = 0) in vec2 corner;
+
+layout (location = 1) in mat4 model;
+layout (location = 5) in float opacity;
+
+out vec2 v_corner;
+out float v_opacity;
+
+layout (std140) uniform camera {
+    mat4 vpMatrix;
+    mat4 viewMatrix;
+    float aspectRatio;
+};
+
+void main() {
+    v_corner = corner;
+    v_opacity = opacity;
+    gl_Position = vpMatrix * model * vec4(corner * 0.5, 0.0, 1.0);
+}
+"#;
+
+const FRAG: &str = r#"#version 300 es
+precision highp float;
+
+in vec2 v_corner;
+in float v_opacity;
+
+out vec4 outColor;
+
+void main() {
+    // solid in the middle and fading out toward the edge of the quad
+    float alpha = v_opacity * (1.0 - smoothstep(0.3, 1.0, length(v_corner)));
+    outColor = vec4(0.0, 0.0, 0.0, alpha);
+}
+"#;
+
+/// Shadow lying flat on the ground at `ground` height under `ball`. The shadow shrinks and fades
+/// as the ball rises.
+pub fn ball_shadow(ball: Vec3, ground: f32) -> Instance {
+    let [x, y, z] = ball;
+    let falloff = 1. / (1. + (y - ground).max(0.));
+    let s = SIZE * falloff;
+    let (n, h) = (-s, ground + LIFT);
+    Instance {
+        model: [
+            s, 0., 0., 0., //
+            0., 0., n, 0., //
+            0., s, 0., 0., //
+            x, h, z, 1., //
+        ],
+        opacity: 0.3 + 0.3 * falloff,
+    }
+}
+
+impl ShaderImpl<Instance> for ShadowShaderImpl {
+    const INSTANCE_CAPACITY: Option<usize> = None;
+
+    fn new() -> Self {
+        Self {}
+    }
+
+    fn get_static_instances(&self) -> Option<Vec<Instance>> {
+        None
+    }
+
+    fn init(&self, shader: &mut ShaderController) -> Result<(), JsValue> {
+        shader.compile(VERT, FRAG)?;
+        shader.bind_uniform_blocks(vec!["camera"])?;
+        shader.layout_buffer::<Vertex>("vertex", 0, vec![("corner", 2)])?;
+        shader.layout_buffer::<Instance>("instance", 1, vec![("model", 16), ("opacity", 1)])?;
+        unsafe {
+            shader.buffer_data_static("vertex", &VERTICES)?;
+        }
+        Ok(())
+    }
+
+    fn get_texture_map(&self) -> Vec<(u32, u32, &'static str)> {
+        vec![]
+    }
+
+    fn draw(&self, ctx: &WebGl2RenderingContext, _: f32, instance_len: i32) {
+        // Shadows are drawn before the sprites standing on them, and must not hide them.
+        ctx.depth_mask(false);
+        ctx.draw_arrays_instanced(
+            WebGl2RenderingContext::TRIANGLES,
+            0,
+            VERTICES.len() as i32,
+            instance_len,
+        );
+        ctx.depth_mask(true);
+    }
+}
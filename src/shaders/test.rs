@@ -1,5 +1,5 @@
 use crate::shader::ConvertArrayView;
-use crate::shader::ShaderController;
+use crate::shader::{load_texture, ShaderController};
 
 use wasm_bindgen::JsValue;
 use web_sys::WebGl2RenderingContext;
@@ -122,8 +122,10 @@ void main() {
             },
         ]);
 
+        let doc = shader.shared.borrow().doc.clone();
+        let source = load_texture(&doc, "sample_texture.png", None).await?;
         shader.activate();
-        shader.create_texture("sample_texture.png").await?;
+        shader.create_texture("sample_texture.png", source)?;
         unsafe {
             shader.buffer_data_dynamic("instance", self.instances.as_ref().unwrap())?;
         }
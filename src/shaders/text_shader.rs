@@ -0,0 +1,115 @@
+use crate::shader::{Shader, ShaderController, ShaderImpl};
+use crate::ConvertArrayView;
+
+use wasm_bindgen::JsValue;
+use web_sys::WebGl2RenderingContext;
+
+/// Draws glyphs of the bitmap font over the scene in the pixels of the UI camera.
+pub type TextShader = Shader<TextShaderImpl, Glyph>;
+/// Page texture of the font. Fonts drawn with the shader must name it as their page.
+pub const FONT_PAGE: &str = "font.png";
+pub struct TextShaderImpl {}
+
+#[repr(C)]
+struct Vertex {
+    /// Corner of the glyph from the top left.
+    corner: [f32; 2],
+}
+
+#[repr(C)]
+pub struct Glyph {
+    /// Top left and size in pixels.
+    pub rect: [f32; 4],
+    /// Top left and size in texture coordinates.
+    pub uv_rect: [f32; 4],
+    pub color: [f32; 4],
+}
+
+impl ConvertArrayView for [Vertex; 6] {}
+static VERTICES: [Vertex; 6] = [
+    Vertex { corner: [0., 1.] },
+    Vertex { corner: [1., 1.] },
+    Vertex { corner: [0., 0.] },
+    Vertex { corner: [0., 0.] },
+    Vertex { corner: [1., 1.] },
+    Vertex { corner: [1., 0.] },
+];
+
+const VERT: &str = r#"#version 300 es
+layout (location = 0) in vec2 corner;
+
+layout (location = 1) in vec4 rect;
+layout (location = 2) in vec4 uv_rect;
+layout (location = 3) in vec4 color;
+
+out vec2 v_uv;
+out vec4 v_color;
+
+layout (std140) uniform ui_camera {
+    mat4 uiMatrix;
+};
+
+void main() {
+    v_uv = uv_rect.xy + corner * uv_rect.zw;
+    v_color = color;
+    gl_Position = uiMatrix * vec4(rect.xy + corner * rect.zw, 0.0, 1.0);
+}
+"#;
+
+const FRAG: &str = r#"#version 300 es
+precision highp float;
+uniform sampler2D tex0;
+
+in vec2 v_uv;
+in vec4 v_color;
+
+out vec4 outColor;
+
+void main() {
+    // glyphs are white on the page, so only the coverage is taken from it
+    float alpha = texture(tex0, v_uv).a * v_color.a;
+    outColor = vec4(v_color.rgb * alpha, alpha);
+}
+"#;
+
+impl ShaderImpl<Glyph> for TextShaderImpl {
+    const INSTANCE_CAPACITY: Option<usize> = None;
+
+    fn new() -> Self {
+        Self {}
+    }
+
+    fn get_static_instances(&self) -> Option<Vec<Glyph>> {
+        None
+    }
+
+    fn init(&self, shader: &mut ShaderController) -> Result<(), JsValue> {
+        shader.compile(VERT, FRAG)?;
+        shader.bind_uniform_blocks(vec!["ui_camera"])?;
+        shader.layout_buffer::<Vertex>("vertex", 0, vec![("corner", 2)])?;
+        shader.layout_buffer::<Glyph>(
+            "instance",
+            1,
+            vec![("rect", 4), ("uv_rect", 4), ("color", 4)],
+        )?;
+        unsafe {
+            shader.buffer_data_static("vertex", &VERTICES)?;
+        }
+        Ok(())
+    }
+
+    fn get_texture_map(&self) -> Vec<(u32, u32, &'static str)> {
+        vec![(0, 0, FONT_PAGE)]
+    }
+
+    fn draw(&self, ctx: &WebGl2RenderingContext, _: f32, instance_len: i32) {
+        ctx.disable(WebGl2RenderingContext::DEPTH_TEST);
+        ctx.draw_arrays_instanced(
+            WebGl2RenderingContext::TRIANGLES,
+            0,
+            VERTICES.len() as i32,
+            instance_len,
+        );
+        ctx.enable(WebGl2RenderingContext::DEPTH_TEST);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::shader::{Shader, ShaderController, ShaderImpl};
+use crate::shaders::entity_shader::Instance;
+use crate::ConvertArrayView;
+
+use wasm_bindgen::JsValue;
+use web_sys::WebGl2RenderingContext;
+use webgl_matrix::Mat4;
+
+/// Draws sprites over the scene in screen space. Sprites are drawn in the pushed order without the
+/// depth test.
+pub type UiShader = Shader<UiShaderImpl, Sprite>;
+pub struct UiShaderImpl {}
+
+#[repr(C)]
+struct Vertex {
+    position: [f32; 2],
+    uv: [f32; 2],
+}
+
+/// Frame of a sprite placed by a model in pixels.
+#[repr(C)]
+pub struct Sprite {
+    pub model: Mat4,
+    pub uv_offset: [f32; 2],
+    pub uv_scale: [f32; 2],
+    pub pos_offset: [f32; 2],
+    pub opacity: f32,
+}
+
+/// Attributes of `Sprite` in the order of its fields, as numbers of floats.
+const SPRITE_LAYOUT: [(&str, i32); 5] = [
+    ("model", 16),
+    ("uv_offset", 2),
+    ("uv_scale", 2),
+    ("pos_offset", 2),
+    ("opacity", 1),
+];
+
+/// Draws an instance of an entity on the screen, taking its model as pixels. Billboarding only
+/// applies in the world and is dropped.
+impl From<Instance> for Sprite {
+    fn from(instance: Instance) -> Self {
+        Self {
+            model: instance.model,
+            uv_offset: instance.uv_offset,
+            uv_scale: instance.uv_scale,
+            pos_offset: instance.pos_offset,
+            opacity: instance.opacity,
+        }
+    }
+}
+
+impl ConvertArrayView for [Vertex; 6] {}
+static VERTICES: [Vertex; 6] = [
+    Vertex {
+        position: [-1., -1.],
+        uv: [0.0, 1.0],
+    },
+    Vertex {
+        position: [1., -1.],
+        uv: [1.0, 1.0],
+    },
+    Vertex {
+        position: [-1., 1.],
+        uv: [0.0, 0.0],
+    },
+    Vertex {
+        position: [-1., 1.],
+        uv: [0.0, 0.0],
+    },
+    Vertex {
+        position: [1., -1.],
+        uv: [1.0, 1.0],
+    },
+    Vertex {
+        position: [1., 1.],
+        uv: [1.0, 0.0],
+    },
+];
+
+const VERT: &str = r#"#version 300 es
+layout (location = 0) in vec2 position;
+layout (location = 1) in vec2 uv;
+
+layout (location = 2) in mat4 model;
+layout (location = 6) in vec2 uv_offset;
+layout (location = 7) in vec2 uv_scale;
+layout (location = 8) in vec2 pos_offset;
+layout (location = 9) in float opacity;
+
+out vec2 v_uv;
+out float v_opacity;
+
+layout (std140) uniform ui_camera {
+    mat4 uiMatrix;
+};
+
+void main() {
+    v_uv = uv_scale * uv + uv_offset;
+    v_opacity = opacity;
+    vec2 local = (position + pos_offset) * uv_scale;
+    // sprites are drawn Y-up while the screen is Y-down
+    gl_Position = uiMatrix * model * vec4(local.x, -local.y, 0.0, 1.0);
+}
+"#;
+
+const FRAG: &str = r#"#version 300 es
+precision highp float;
+uniform sampler2D tex0;
+
+in vec2 v_uv;
+in float v_opacity;
+
+out vec4 outColor;
+
+void main() {
+    vec4 tex_color = texture(tex0, v_uv);
+    float alpha = tex_color.a * v_opacity;
+    outColor = vec4(tex_color.rgb * alpha, alpha);
+}
+"#;
+
+impl ShaderImpl<Sprite> for UiShaderImpl {
+    const INSTANCE_CAPACITY: Option<usize> = None;
+
+    fn new() -> Self {
+        Self {}
+    }
+
+    fn get_static_instances(&self) -> Option<Vec<Sprite>> {
+        None
+    }
+
+    fn init(&self, shader: &mut ShaderController) -> Result<(), JsValue> {
+        shader.compile(VERT, FRAG)?;
+        shader.bind_uniform_blocks(vec!["ui_camera"])?;
+        shader.layout_buffer::<Vertex>("vertex", 0, vec![("position", 2), ("uv", 2)])?;
+        shader.layout_buffer::<Sprite>("instance", 1, SPRITE_LAYOUT.to_vec())?;
+        unsafe {
+            shader.buffer_data_static("vertex", &VERTICES)?;
+        }
+        Ok(())
+    }
+
+    fn get_texture_map(&self) -> Vec<(u32, u32, &'static str)> {
+        vec![(0, 0, "entities0.png")]
+    }
+
+    fn get_atlas_map(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("entities0.png", "entities0.atlas.json")]
+    }
+
+    fn draw(&self, ctx: &WebGl2RenderingContext, _: f32, instance_len: i32) {
+        ctx.disable(WebGl2RenderingContext::DEPTH_TEST);
+        ctx.draw_arrays_instanced(
+            WebGl2RenderingContext::TRIANGLES,
+            0,
+            VERTICES.len() as i32,
+            instance_len,
+        );
+        ctx.enable(WebGl2RenderingContext::DEPTH_TEST);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sprite_layout_covers_every_byte() {
+        let floats: i32 = SPRITE_LAYOUT.iter().map(|(_, len)| len).sum();
+        assert_eq!(floats as usize * 4, std::mem::size_of::<Sprite>());
+    }
+}
@@ -0,0 +1,252 @@
+use crate::game_state::{AtBatResult, HitResult, PitchCall, Trajectory};
+
+use webgl_matrix::{Mat4, Matrix, MulVectorMatrix, Vec3};
+
+pub struct ContactRecord {
+    pub exit_velocity: f32,
+    pub launch_angle: f32,
+    pub landing: Vec3,
+    pub result: HitResult,
+}
+
+pub struct PitchRecord {
+    pub call: PitchCall,
+    pub at_bat: Option<AtBatResult>,
+    pub contact: Option<ContactRecord>,
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct StatTotals {
+    pub pitches: u32,
+    pub swings: u32,
+    pub whiffs: u32,
+    pub plate_appearances: u32,
+    pub at_bats: u32,
+    pub hits: u32,
+    pub home_runs: u32,
+}
+
+/// Every pitch of the session and the rates aggregated from them.
+pub struct SessionStats {
+    records: Vec<PitchRecord>,
+}
+
+impl ContactRecord {
+    pub fn new(trajectory: &Trajectory) -> Self {
+        Self {
+            exit_velocity: trajectory.exit_velocity,
+            launch_angle: trajectory.launch_angle,
+            landing: trajectory.landing,
+            result: trajectory.result.clone(),
+        }
+    }
+}
+
+impl StatTotals {
+    pub fn add(&self, other: &Self) -> Self {
+        Self {
+            pitches: self.pitches + other.pitches,
+            swings: self.swings + other.swings,
+            whiffs: self.whiffs + other.whiffs,
+            plate_appearances: self.plate_appearances + other.plate_appearances,
+            at_bats: self.at_bats + other.at_bats,
+            hits: self.hits + other.hits,
+            home_runs: self.home_runs + other.home_runs,
+        }
+    }
+
+    /// Hits per at-bat. Walks are not at-bats.
+    pub fn batting_average(&self) -> f32 {
+        ratio(self.hits, self.at_bats)
+    }
+
+    /// Home runs per plate appearance.
+    pub fn home_run_rate(&self) -> f32 {
+        ratio(self.home_runs, self.plate_appearances)
+    }
+
+    /// Swings that missed the ball per swing.
+    pub fn whiff_rate(&self) -> f32 {
+        ratio(self.whiffs, self.swings)
+    }
+}
+
+impl SessionStats {
+    pub fn new() -> Self {
+        Self { records: vec![] }
+    }
+
+    pub fn record(&mut self, record: PitchRecord) {
+        self.records.push(record);
+    }
+
+    /// Counts of the session, which can be accumulated over sessions.
+    pub fn totals(&self) -> StatTotals {
+        let mut totals = StatTotals::default();
+        for record in &self.records {
+            totals.pitches += 1;
+            match record.call {
+                PitchCall::SwingingStrike => {
+                    totals.swings += 1;
+                    totals.whiffs += 1;
+                }
+                PitchCall::Foul | PitchCall::InPlay(_) => totals.swings += 1,
+                _ => {}
+            }
+            match record.at_bat {
+                Some(AtBatResult::Walk) => totals.plate_appearances += 1,
+                Some(AtBatResult::Strikeout) => {
+                    totals.plate_appearances += 1;
+                    totals.at_bats += 1;
+                }
+                Some(AtBatResult::InPlay(ref result)) => {
+                    totals.plate_appearances += 1;
+                    totals.at_bats += 1;
+                    totals.hits += 1;
+                    if let HitResult::HomeRun = result {
+                        totals.home_runs += 1;
+                    }
+                }
+                None => {}
+            }
+        }
+        totals
+    }
+
+    fn contacts(&self) -> impl Iterator<Item = &ContactRecord> {
+        self.records
+            .iter()
+            .filter_map(|record| record.contact.as_ref())
+    }
+
+    /// Average of exit velocity and launch angle in degrees over batted balls.
+    pub fn average_launch(&self) -> (f32, f32) {
+        let (count, velocity, angle) = self.contacts().fold((0, 0., 0.), |(n, v, a), contact| {
+            (n + 1, v + contact.exit_velocity, a + contact.launch_angle)
+        });
+        if count == 0 {
+            (0., 0.)
+        } else {
+            let count = count as f32;
+            (velocity / count, (angle / count).to_degrees())
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        let totals = self.totals();
+        let (exit_velocity, launch_angle) = self.average_launch();
+        format!(
+            "AVG {:.3} / HR% {:.1} / Whiff% {:.1} / EV {:.1} / LA {:.0}°",
+            totals.batting_average(),
+            totals.home_run_rate() * 100.,
+            totals.whiff_rate() * 100.,
+            exit_velocity,
+            launch_angle,
+        )
+    }
+
+    /// Draws the landing points of batted balls from above as SVG. Points are converted into the
+    /// field coordinate, which `field_model` maps into the world as `Background::model` does.
+    pub fn spray_chart(&self, field_model: &Mat4) -> String {
+        let mut world_to_field = *field_model;
+        if world_to_field.inverse().is_none() {
+            return String::new();
+        }
+        let points = self
+            .contacts()
+            .map(|contact| {
+                let [x, z] = to_field(&world_to_field, &contact.landing);
+                let color = match contact.result {
+                    HitResult::Foul => "gray",
+                    HitResult::SafeHit => "white",
+                    HitResult::HomeRun => "gold",
+                };
+                // the field's z axis points upward on the chart
+                format!(
+                    r#"<circle cx="{:.3}" cy="{:.3}" r="0.015" fill="{}"/>"#,
+                    x, -z, color,
+                )
+            })
+            .collect::<String>();
+        format!(
+            r#"<svg viewBox="-0.5 -0.5 1 1" width="160" height="160"><rect x="-0.5" y="-0.5" width="1" height="1" fill="darkgreen"/>{}</svg>"#,
+            points,
+        )
+    }
+}
+
+/// Point of the field coordinate on the ground under `point`, as (x, z).
+fn to_field(world_to_field: &Mat4, &[x, y, z]: &Vec3) -> [f32; 2] {
+    let p = [x, y, z, 1.].mul_matrix(world_to_field);
+    [p[0] / p[3], p[2] / p[3]]
+}
+
+fn ratio(count: u32, total: u32) -> f32 {
+    if total == 0 {
+        0.
+    } else {
+        count as f32 / total as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn landing_point_is_mapped_into_the_field() {
+        // `Background::model` of the batting scene
+        let mut world_to_field: Mat4 = [
+            9., 0., 0., 0., //
+            0., 9., 0., 0., //
+            0., 0., -10., 0., //
+            0., -1., -3., 1., //
+        ];
+        world_to_field.inverse().unwrap();
+        let [x, z] = to_field(&world_to_field, &[4.5, -1., -8.]);
+        assert!((x - 0.5).abs() < 1e-5);
+        assert!((z - 0.5).abs() < 1e-5);
+    }
+
+    fn pitch(call: PitchCall, at_bat: Option<AtBatResult>) -> PitchRecord {
+        PitchRecord {
+            call,
+            at_bat,
+            contact: None,
+        }
+    }
+
+    #[test]
+    fn totals_and_rates_follow_the_scoring_rules() {
+        let mut stats = SessionStats::new();
+        assert!(stats.totals() == StatTotals::default());
+        assert_eq!(stats.totals().batting_average(), 0.);
+
+        // ball four
+        stats.record(pitch(PitchCall::Ball, Some(AtBatResult::Walk)));
+        // foul, swing and miss, then strike three looking
+        stats.record(pitch(PitchCall::Foul, None));
+        stats.record(pitch(PitchCall::SwingingStrike, None));
+        stats.record(pitch(PitchCall::CalledStrike, Some(AtBatResult::Strikeout)));
+        stats.record(pitch(
+            PitchCall::InPlay(HitResult::SafeHit),
+            Some(AtBatResult::InPlay(HitResult::SafeHit)),
+        ));
+        stats.record(pitch(
+            PitchCall::InPlay(HitResult::HomeRun),
+            Some(AtBatResult::InPlay(HitResult::HomeRun)),
+        ));
+
+        let totals = stats.totals();
+        assert_eq!(totals.pitches, 6);
+        assert_eq!(totals.swings, 4);
+        assert_eq!(totals.whiffs, 1);
+        assert_eq!(totals.plate_appearances, 4);
+        assert_eq!(totals.at_bats, 3);
+        assert_eq!(totals.hits, 2);
+        assert_eq!(totals.home_runs, 1);
+        assert!((totals.batting_average() - 2. / 3.).abs() < 1e-6);
+        assert!((totals.whiff_rate() - 0.25).abs() < 1e-6);
+        assert!((totals.home_run_rate() - 0.25).abs() < 1e-6);
+    }
+}
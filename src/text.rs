@@ -0,0 +1,202 @@
+use crate::shader::BitmapFont;
+use crate::shaders::text_shader::Glyph;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Clone, Copy)]
+pub struct TextStyle {
+    /// Which side of each line the origin is on.
+    pub align: Align,
+    pub color: [f32; 4],
+    /// Pixels per pixel of the font.
+    pub scale: f32,
+    /// Lines longer than this in pixels are wrapped between words.
+    pub max_width: Option<f32>,
+}
+
+impl TextStyle {
+    pub fn new(align: Align, color: [f32; 4], scale: f32) -> Self {
+        Self {
+            align,
+            color,
+            scale,
+            max_width: None,
+        }
+    }
+
+    pub fn with_max_width(mut self, max_width: f32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+}
+
+/// Lays out `text` into glyphs from `origin`, which is the top of the first line. Lines are broken
+/// at `\n` as well as by wrapping.
+pub fn layout_text(
+    font: &BitmapFont,
+    text: &str,
+    origin: [f32; 2],
+    style: &TextStyle,
+) -> Vec<Glyph> {
+    let mut glyphs = vec![];
+    let line_height = font.line_height * style.scale;
+    for (i, line) in wrap(font, text, style).iter().enumerate() {
+        let width = measure(font, line) * style.scale;
+        let x = match style.align {
+            Align::Left => origin[0],
+            Align::Center => origin[0] - width / 2.,
+            Align::Right => origin[0] - width,
+        };
+        let y = origin[1] + line_height * i as f32;
+        glyphs.extend(line_glyphs(font, line, [x, y], style));
+    }
+    glyphs
+}
+
+/// Width of `line` in pixels of the font.
+fn measure(font: &BitmapFont, line: &str) -> f32 {
+    let mut width = 0.;
+    let mut prev = None;
+    for c in line.chars() {
+        if let Some(glyph) = font.glyph(c) {
+            width += glyph.advance + prev.map_or(0., |prev| font.kerning(prev, c));
+        }
+        prev = Some(c);
+    }
+    width
+}
+
+fn line_glyphs(font: &BitmapFont, line: &str, pen: [f32; 2], style: &TextStyle) -> Vec<Glyph> {
+    let [scale_w, scale_h] = font.scale;
+    let s = style.scale;
+    let [mut x, y] = pen;
+    let mut glyphs = vec![];
+    let mut prev = None;
+    for c in line.chars() {
+        let glyph = match font.glyph(c) {
+            Some(glyph) => glyph,
+            None => continue,
+        };
+        x += prev.map_or(0., |prev| font.kerning(prev, c)) * s;
+        prev = Some(c);
+        if glyph.width > 0. && glyph.height > 0. {
+            glyphs.push(Glyph {
+                rect: [
+                    x + glyph.offset[0] * s,
+                    y + glyph.offset[1] * s,
+                    glyph.width * s,
+                    glyph.height * s,
+                ],
+                uv_rect: [
+                    glyph.x / scale_w,
+                    glyph.y / scale_h,
+                    glyph.width / scale_w,
+                    glyph.height / scale_h,
+                ],
+                color: style.color,
+            });
+        }
+        x += glyph.advance * s;
+    }
+    glyphs
+}
+
+/// Splits `text` into lines, breaking between words where a line gets longer than
+/// `TextStyle::max_width`. A word longer than the width is left on a line of its own.
+fn wrap(font: &BitmapFont, text: &str, style: &TextStyle) -> Vec<String> {
+    let max_width = match style.max_width {
+        Some(max_width) => max_width / style.scale,
+        None => return text.lines().map(|line| line.to_string()).collect(),
+    };
+    let mut lines = vec![];
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", line, word)
+            };
+            if !line.is_empty() && measure(font, &candidate) > max_width {
+                lines.push(line);
+                line = word.to_string();
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 'A' is 10 pixels wide, a space 5, and "AV" is kerned by -2.
+    const FONT: &str = r#"common lineHeight=20 base=16 scaleW=100 scaleH=100 pages=1
+page id=0 file="test.png"
+char id=65 x=0 y=0 width=10 height=16 xoffset=0 yoffset=0 xadvance=10 page=0 chnl=15
+char id=86 x=10 y=0 width=10 height=16 xoffset=0 yoffset=0 xadvance=10 page=0 chnl=15
+char id=32 x=0 y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=5 page=0 chnl=15
+kerning first=65 second=86 amount=-2
+"#;
+
+    fn font() -> BitmapFont {
+        BitmapFont::parse(FONT).unwrap()
+    }
+
+    fn left_edges(align: Align) -> Vec<f32> {
+        let style = TextStyle::new(align, [1.; 4], 2.);
+        layout_text(&font(), "AV", [100., 50.], &style)
+            .iter()
+            .map(|glyph| glyph.rect[0])
+            .collect()
+    }
+
+    #[test]
+    fn lines_are_aligned_to_the_origin() {
+        // "AV" is 18 pixels wide in the font and 36 at the scale of 2
+        assert_eq!(left_edges(Align::Left), vec![100., 116.]);
+        assert_eq!(left_edges(Align::Center), vec![82., 98.]);
+        assert_eq!(left_edges(Align::Right), vec![64., 80.]);
+    }
+
+    #[test]
+    fn glyphs_are_placed_on_their_lines() {
+        let style = TextStyle::new(Align::Left, [1.; 4], 2.);
+        let glyphs = layout_text(&font(), "A\nA A", [0., 10.], &style);
+        // spaces have no glyph
+        assert_eq!(glyphs.len(), 3);
+        assert_eq!(glyphs[0].rect, [0., 10., 20., 32.]);
+        assert_eq!(glyphs[1].rect, [0., 50., 20., 32.]);
+        assert_eq!(glyphs[2].rect, [30., 50., 20., 32.]);
+        assert_eq!(glyphs[0].uv_rect, [0., 0., 0.1, 0.16]);
+    }
+
+    #[test]
+    fn words_wrap_at_the_max_width() {
+        let font = font();
+        // "A A" is exactly 25 pixels wide
+        let style = TextStyle::new(Align::Left, [1.; 4], 1.).with_max_width(25.);
+        assert_eq!(wrap(&font, "A A A", &style), vec!["A A", "A"]);
+        assert_eq!(wrap(&font, "A A\nA", &style), vec!["A A", "A"]);
+        // the width is in pixels of the screen
+        let scaled = TextStyle::new(Align::Left, [1.; 4], 2.).with_max_width(50.);
+        assert_eq!(wrap(&font, "A A A", &scaled), vec!["A A", "A"]);
+        // without a width lines are only broken at '\n'
+        let unlimited = TextStyle::new(Align::Left, [1.; 4], 1.);
+        assert_eq!(wrap(&font, "A A A\nA", &unlimited), vec!["A A A", "A"]);
+    }
+
+    #[test]
+    fn word_longer_than_the_max_width_gets_a_line_of_its_own() {
+        let style = TextStyle::new(Align::Left, [1.; 4], 1.).with_max_width(25.);
+        assert_eq!(wrap(&font(), "A AVAVA A", &style), vec!["A", "AVAVA", "A"]);
+    }
+}
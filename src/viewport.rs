@@ -0,0 +1,112 @@
+use core::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlCanvasElement, ResizeObserver, ResizeObserverEntry, Window};
+
+/// Narrowest and widest aspect ratios drawn. Bars are left on the sides of wider canvases and
+/// above and below narrower ones.
+const MIN_ASPECT: f32 = 3. / 4.;
+const MAX_ASPECT: f32 = 16. / 9.;
+
+/// Height of the canvas and the area the game is drawn in, both in device pixels. The area is in
+/// the coordinate of `gl.viewport`, whose origin is at the bottom left.
+pub struct Viewport {
+    pub height: u32,
+    pub area: [i32; 4],
+    pub device_pixel_ratio: f32,
+}
+
+impl Viewport {
+    fn new(width: u32, height: u32, device_pixel_ratio: f32) -> Self {
+        let (w, h) = (width as f32, height as f32);
+        let aspect = if h > 0. { w / h } else { 1. };
+        let (area_w, area_h) = if aspect > MAX_ASPECT {
+            (h * MAX_ASPECT, h)
+        } else if aspect < MIN_ASPECT {
+            (w, w / MIN_ASPECT)
+        } else {
+            (w, h)
+        };
+        Self {
+            height,
+            area: [
+                ((w - area_w) / 2.).round() as i32,
+                ((h - area_h) / 2.).round() as i32,
+                area_w.round() as i32,
+                area_h.round() as i32,
+            ],
+            device_pixel_ratio,
+        }
+    }
+
+    pub fn aspect(&self) -> f32 {
+        let [_, _, w, h] = self.area;
+        if h > 0 {
+            w as f32 / h as f32
+        } else {
+            1.
+        }
+    }
+
+    /// Size of the area in CSS pixels.
+    pub fn css_size(&self) -> (f32, f32) {
+        let [_, _, w, h] = self.area;
+        (
+            w as f32 / self.device_pixel_ratio,
+            h as f32 / self.device_pixel_ratio,
+        )
+    }
+
+    /// Converts a point given in CSS pixels from the top left of the canvas into normalized device
+    /// coordinates of the area.
+    pub fn to_ndc(&self, css_x: f32, css_y: f32) -> (f32, f32) {
+        let [x, y, w, h] = self.area;
+        let px = css_x * self.device_pixel_ratio - x as f32;
+        // flip into the bottom-left origin
+        let py = (self.height as f32 - css_y * self.device_pixel_ratio) - y as f32;
+        (
+            2. * px / w.max(1) as f32 - 1.,
+            2. * py / h.max(1) as f32 - 1.,
+        )
+    }
+}
+
+/// Keeps the backing store of `canvas` as large as it is shown on the device and returns the
+/// viewport following it.
+pub fn watch_viewport(
+    window: Rc<Window>,
+    canvas: Rc<HtmlCanvasElement>,
+) -> Result<Rc<RefCell<Viewport>>, JsValue> {
+    let viewport = Rc::new(RefCell::new(Viewport::new(
+        canvas.width(),
+        canvas.height(),
+        1.,
+    )));
+
+    let onresize = Closure::wrap(Box::new({
+        let viewport = viewport.clone();
+        let canvas = canvas.clone();
+        move |entries: js_sys::Array, _: ResizeObserver| {
+            let entry = match entries.get(0).dyn_into::<ResizeObserverEntry>() {
+                Ok(entry) => entry,
+                Err(_) => return,
+            };
+            let rect = entry.content_rect();
+            let device_pixel_ratio = window.device_pixel_ratio() as f32;
+            let width = (rect.width() as f32 * device_pixel_ratio).round() as u32;
+            let height = (rect.height() as f32 * device_pixel_ratio).round() as u32;
+            if width == 0 || height == 0 {
+                return;
+            }
+            canvas.set_width(width);
+            canvas.set_height(height);
+            *viewport.borrow_mut() = Viewport::new(width, height, device_pixel_ratio);
+        }
+    }) as Box<dyn FnMut(js_sys::Array, ResizeObserver)>);
+    let observer = ResizeObserver::new(onresize.as_ref().unchecked_ref())?;
+    observer.observe(&canvas);
+    onresize.forget();
+
+    Ok(viewport)
+}